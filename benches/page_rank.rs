@@ -7,7 +7,7 @@ use std::rc::Rc;
 type N = Node<usize, Cell<f64>, Rc<Cell<f64>>>;
 type G = Vec<N>;
 
-pub fn page_rank(g: &G, α: f64, ε: f64) {
+pub fn page_rank(g: &G, α: f64, ε: f64) -> f64 {
     let inverse = 1.0 / g.len() as f64;
     for node in g {
         let sum = node.iter_out().map(|e| e.value().get()).sum::<f64>();
@@ -38,6 +38,7 @@ pub fn page_rank(g: &G, α: f64, ε: f64) {
         Δ = g.iter().map(|u| (u.get() - nodes[*u.key()]).abs()).sum();
         nodes.iter_mut().for_each(|x| *x = 0.0);
     }
+    Δ
 }
 
 pub fn create_page_rank_dataset(size: usize, avg_dgr: usize, min_dgr: usize, max_dgr: usize) -> G {
@@ -15,8 +15,8 @@ pub fn create_graph_vec_distance_1(size: usize) -> Vec<digraph::Node<usize, Cell
         let neighbour_count = i % 8 + 3;
         let j_from = max(0, i - neighbour_count / 2);
         let j_to = min(size, j_from + neighbour_count);
-        for j in j_from..j_to {
-            connect!(&node => &g[j], (i + 3) % 10);
+        for other in &g[j_from..j_to] {
+            connect!(&node => other, (i + 3) % 10);
         }
     }
     g
@@ -69,7 +69,7 @@ fn max_flow(g: &G) -> u64 {
         .filter(&mut |Edge(_, _, e)| e.cur() < e.max())
         .search_path()
     {
-        let mut aug_flow = std::u64::MAX;
+        let mut aug_flow = u64::MAX;
 
         // 3. We find the minimum augmenting flow along the path.
         for Edge(_, _, flow) in path.iter_edges() {
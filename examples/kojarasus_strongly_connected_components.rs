@@ -23,7 +23,7 @@ fn ordering(graph: &G) -> G {
                 .filter(&mut |Edge(_, v, _)| !visited.contains(v.key()))
                 .search_nodes();
             for node in &partition {
-                visited.insert(node.key().clone());
+                visited.insert(*node.key());
                 ordering.push(node.clone());
             }
         }
@@ -48,12 +48,12 @@ fn kojarasu(graph: &G) -> Vec<G> {
                     let mut cycle = cycle.to_vec_nodes();
                     cycle.pop();
                     for node in &cycle {
-                        invariant.insert(node.key().clone());
+                        invariant.insert(*node.key());
                     }
                     components.push(cycle);
                 }
                 None => {
-                    invariant.insert(node.key().clone());
+                    invariant.insert(*node.key());
                     components.push(vec![node.clone()]);
                 }
             }
@@ -84,18 +84,15 @@ fn ex1() {
         (7) => []
     ];
 
-    let expect = vec![vec![7], vec![4, 5, 6], vec![0, 1, 2, 3]];
+    let expect = [vec![7], vec![4, 5, 6], vec![0, 1, 2, 3]];
 
     let mut g = g.to_vec();
-    g.sort_by(|a, b| a.key().cmp(&b.key()));
+    g.sort_by(|a, b| a.key().cmp(b.key()));
     let mut components = kojarasu(&g);
 
     for (i, component) in components.iter_mut().enumerate() {
-        component.sort_by(|a, b| a.key().cmp(&b.key()));
-        let keys = component
-            .iter()
-            .map(|node| node.key().clone())
-            .collect::<Vec<_>>();
+        component.sort_by(|a, b| a.key().cmp(b.key()));
+        let keys = component.iter().map(|node| *node.key()).collect::<Vec<_>>();
         assert_eq!(keys, expect[i]);
     }
 }
@@ -123,18 +120,15 @@ fn ex2() {
         (8) => [5, 7, 8]
     ];
 
-    let expect = vec![vec![6, 7], vec![1, 2, 3], vec![8], vec![4, 5]];
+    let expect = [vec![6, 7], vec![1, 2, 3], vec![8], vec![4, 5]];
 
     let mut g = g.to_vec();
-    g.sort_by(|a, b| a.key().cmp(&b.key()));
+    g.sort_by(|a, b| a.key().cmp(b.key()));
     let mut components = kojarasu(&g);
 
     for (i, component) in components.iter_mut().enumerate() {
-        component.sort_by(|a, b| a.key().cmp(&b.key()));
-        let keys = component
-            .iter()
-            .map(|node| node.key().clone())
-            .collect::<Vec<_>>();
+        component.sort_by(|a, b| a.key().cmp(b.key()));
+        let keys = component.iter().map(|node| *node.key()).collect::<Vec<_>>();
         assert_eq!(keys, expect[i]);
     }
 }
@@ -161,18 +155,15 @@ fn ex3() {
         (8) => [5]
     ];
 
-    let expect = vec![vec![5, 6, 7, 8], vec![4], vec![1, 2, 3]];
+    let expect = [vec![5, 6, 7, 8], vec![4], vec![1, 2, 3]];
 
     let mut g = g.to_vec();
-    g.sort_by(|a, b| a.key().cmp(&b.key()));
+    g.sort_by(|a, b| a.key().cmp(b.key()));
     let mut components = kojarasu(&g);
 
     for (i, component) in components.iter_mut().enumerate() {
-        component.sort_by(|a, b| a.key().cmp(&b.key()));
-        let keys = component
-            .iter()
-            .map(|node| node.key().clone())
-            .collect::<Vec<_>>();
+        component.sort_by(|a, b| a.key().cmp(b.key()));
+        let keys = component.iter().map(|node| *node.key()).collect::<Vec<_>>();
         assert_eq!(keys, expect[i]);
     }
 }
@@ -199,18 +190,15 @@ fn ex4() {
         (7) => [3, 5]
     ];
 
-    let expect = vec![vec![0, 1, 2], vec![3, 7], vec![4, 5, 6]];
+    let expect = [vec![0, 1, 2], vec![3, 7], vec![4, 5, 6]];
 
     let mut g = g.to_vec();
-    g.sort_by(|a, b| a.key().cmp(&b.key()));
+    g.sort_by(|a, b| a.key().cmp(b.key()));
     let mut components = kojarasu(&g);
 
     for (i, component) in components.iter_mut().enumerate() {
-        component.sort_by(|a, b| a.key().cmp(&b.key()));
-        let keys = component
-            .iter()
-            .map(|node| node.key().clone())
-            .collect::<Vec<_>>();
+        component.sort_by(|a, b| a.key().cmp(b.key()));
+        let keys = component.iter().map(|node| *node.key()).collect::<Vec<_>>();
         assert_eq!(keys, expect[i]);
     }
 }
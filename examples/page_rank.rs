@@ -164,7 +164,7 @@ fn main() {
             Some(vec![
                 attr("bgcolor", THEME[0]),
                 attr("fontcolor", THEME[3]),
-                attr("label", &format!("Page Ranking on a Barabasi Albert Graph")),
+                attr("label", "Page Ranking on a Barabasi Albert Graph"),
             ])
         },
         &|node| {
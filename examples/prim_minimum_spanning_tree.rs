@@ -59,7 +59,7 @@ fn prim_minimum_spanning_tree(s: &N) -> Vec<E> {
             // ...and the edge's destination node `v` is not in the MST,
             // then we add the edge to the MST and add all edges
             // in `tmp` back to the heap.
-            if in_mst.contains(v.key()) == false {
+            if !in_mst.contains(v.key()) {
                 in_mst.insert(*v.key());
                 mst.push(edge.clone());
                 for tmp_edge in &tmp {
@@ -71,7 +71,7 @@ fn prim_minimum_spanning_tree(s: &N) -> Vec<E> {
             // it's source node `u` nor it's destination node `v` are
             // in the MST, so we store the edge and continue to the next
             // iteration.
-            if in_mst.contains(v.key()) == false {
+            if !in_mst.contains(v.key()) {
                 tmp.push(edge);
             }
         }
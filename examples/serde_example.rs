@@ -19,8 +19,8 @@ fn main() {
     let json = serde_json::to_vec(&graph).unwrap();
 
     // CBOR's binary representation result's in a smaller size than JSON's
-    assert!(cbor.len() == 47);
-    assert!(json.len() == 101);
+    assert!(cbor.len() == 48);
+    assert!(json.len() == 104);
 
     let graph_cbor: Graph<&str, (), i32> = serde_cbor::from_slice(&cbor).unwrap();
     let graph_json: Graph<&str, (), i32> = serde_json::from_slice(&json).unwrap();
@@ -0,0 +1,415 @@
+use super::*;
+use std::collections::{HashMap, VecDeque};
+
+/// PageRank computation over a graph. Configured with builder methods and
+/// run with `compute`. By default the damping factor is `0.85`, the
+/// tolerance `1e-6`, at most `100` iterations are run, every edge has the
+/// same weight and the teleport distribution is uniform.
+pub struct PageRank<'a, K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    graph: &'a Graph<K, N, E>,
+    damping: f64,
+    tolerance: f64,
+    max_iterations: usize,
+    personalization: Option<HashMap<K, f64>>,
+    weight: Option<&'a dyn Fn(&E) -> f64>,
+}
+
+impl<'a, K, N, E> PageRank<'a, K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    pub fn new(graph: &'a Graph<K, N, E>) -> Self {
+        PageRank {
+            graph,
+            damping: 0.85,
+            tolerance: 1e-6,
+            max_iterations: 100,
+            personalization: None,
+            weight: None,
+        }
+    }
+
+    /// Probability of following an edge instead of teleporting.
+    pub fn damping(mut self, damping: f64) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// The iteration stops once the ranks change less than this in total.
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Teleport distribution keyed by node key. Missing nodes get zero and
+    /// the values are normalized to sum up to one. If they don't sum up to
+    /// a positive number the uniform distribution is used instead. Dangling
+    /// nodes also distribute their rank according to it.
+    pub fn personalization(mut self, personalization: HashMap<K, f64>) -> Self {
+        self.personalization = Some(personalization);
+        self
+    }
+
+    /// Weight of each edge computed from the edge value. The rank of a node
+    /// is split between its outbound edges in proportion to their weights.
+    pub fn weight(mut self, weight: &'a dyn Fn(&E) -> f64) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    pub fn compute(&self) -> HashMap<K, f64> {
+        let (index, edges, endpoints) = self.graph.index_edges();
+        let n = index.len();
+        if n == 0 {
+            return HashMap::default();
+        }
+        let mut keys = vec![None; n];
+        for (k, &i) in index.iter() {
+            keys[i] = Some(k.clone());
+        }
+        let keys = keys.into_iter().flatten().collect::<Vec<_>>();
+
+        let mut teleport = match &self.personalization {
+            Some(p) => keys.iter().map(|k| *p.get(k).unwrap_or(&0.0)).collect(),
+            None => vec![1.0; n],
+        };
+        let sum = teleport.iter().sum::<f64>();
+        if sum > 0.0 {
+            teleport.iter_mut().for_each(|t| *t /= sum);
+        } else {
+            teleport = vec![1.0 / n as f64; n];
+        }
+
+        let weights = edges
+            .iter()
+            .map(|e| self.weight.map_or(1.0, |w| w(e.value())))
+            .collect::<Vec<_>>();
+        let mut out_weight = vec![0.0; n];
+        for (&(u, _), w) in endpoints.iter().zip(&weights) {
+            out_weight[u] += w;
+        }
+
+        let mut rank = teleport.clone();
+        for _ in 0..self.max_iterations {
+            let dangling = (0..n)
+                .filter(|&u| out_weight[u] == 0.0)
+                .map(|u| rank[u])
+                .sum::<f64>();
+            let mut next = teleport
+                .iter()
+                .map(|t| (1.0 - self.damping + self.damping * dangling) * t)
+                .collect::<Vec<_>>();
+            for (&(u, v), w) in endpoints.iter().zip(&weights) {
+                next[v] += self.damping * rank[u] * w / out_weight[u];
+            }
+            let delta = next
+                .iter()
+                .zip(&rank)
+                .map(|(a, b)| (a - b).abs())
+                .sum::<f64>();
+            rank = next;
+            if delta < self.tolerance {
+                break;
+            }
+        }
+        keys.into_iter().zip(rank).collect()
+    }
+}
+
+impl<K, N, E> Graph<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    /// Returns the keys of the graph in the order of their dense index and
+    /// the outbound neighbours of each node by index.
+    fn index_neighbours(&self) -> (Vec<K>, Vec<Vec<usize>>) {
+        let (index, _, endpoints) = self.index_edges();
+        let mut keys = vec![None; index.len()];
+        for (k, &i) in index.iter() {
+            keys[i] = Some(k.clone());
+        }
+        let mut neighbours = vec![vec![]; index.len()];
+        for (u, v) in endpoints {
+            neighbours[u].push(v);
+        }
+        (keys.into_iter().flatten().collect(), neighbours)
+    }
+
+    /// Create a PageRank computation over the graph. Returns the rank of
+    /// each node keyed by node key, the ranks sum up to one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (char)
+    ///     ('A') => ['B']
+    ///     ('B') => ['C']
+    ///     ('C') => ['A']
+    ///     ('D') => ['A']
+    /// ];
+    ///
+    /// let ranks = g.page_rank().damping(0.9).tolerance(1e-9).compute();
+    ///
+    /// assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-6);
+    /// assert!(ranks[&'A'] > ranks[&'B']);
+    /// assert!(ranks[&'D'] < ranks[&'C']);
+    /// ```
+    pub fn page_rank(&self) -> PageRank<'_, K, N, E> {
+        PageRank::new(self)
+    }
+
+    /// Compute the degree centrality of each node, the amount of inbound and
+    /// outbound edges divided by the amount of other nodes in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (char)
+    ///     ('A') => ['B', 'C']
+    ///     ('B') => ['C']
+    ///     ('C') => []
+    /// ];
+    ///
+    /// let centrality = g.degree_centrality();
+    ///
+    /// assert!(centrality[&'A'] == 1.0);
+    /// assert!(centrality[&'C'] == 1.0);
+    /// ```
+    pub fn degree_centrality(&self) -> HashMap<K, f64> {
+        let (keys, neighbours) = self.index_neighbours();
+        let mut degree = vec![0; keys.len()];
+        for (u, list) in neighbours.iter().enumerate() {
+            degree[u] += list.len();
+            for &v in list {
+                degree[v] += 1;
+            }
+        }
+        Self::scale_degree(keys, degree)
+    }
+
+    /// Compute the in-degree centrality of each node, the amount of inbound
+    /// edges divided by the amount of other nodes in the graph.
+    pub fn in_degree_centrality(&self) -> HashMap<K, f64> {
+        let (keys, neighbours) = self.index_neighbours();
+        let mut degree = vec![0; keys.len()];
+        for &v in neighbours.iter().flatten() {
+            degree[v] += 1;
+        }
+        Self::scale_degree(keys, degree)
+    }
+
+    /// Compute the out-degree centrality of each node, the amount of
+    /// outbound edges divided by the amount of other nodes in the graph.
+    pub fn out_degree_centrality(&self) -> HashMap<K, f64> {
+        let (keys, neighbours) = self.index_neighbours();
+        let degree = neighbours.iter().map(|list| list.len()).collect();
+        Self::scale_degree(keys, degree)
+    }
+
+    fn scale_degree(keys: Vec<K>, degree: Vec<usize>) -> HashMap<K, f64> {
+        let others = (keys.len().max(2) - 1) as f64;
+        keys.into_iter()
+            .zip(degree)
+            .map(|(k, d)| (k, d as f64 / others))
+            .collect()
+    }
+
+    /// Compute the closeness centrality of each node. It's the inverse of
+    /// the average distance along outbound edges to the nodes reachable
+    /// from the node, scaled by the fraction of nodes reachable so that
+    /// nodes in small components don't score high.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (char)
+    ///     ('A') => ['B']
+    ///     ('B') => ['C']
+    ///     ('C') => []
+    /// ];
+    ///
+    /// let centrality = g.closeness_centrality();
+    ///
+    /// assert!(centrality[&'A'] == 2.0 / 3.0);
+    /// assert!(centrality[&'B'] == 0.5);
+    /// assert!(centrality[&'C'] == 0.0);
+    /// ```
+    pub fn closeness_centrality(&self) -> HashMap<K, f64> {
+        let (keys, neighbours) = self.index_neighbours();
+        let n = keys.len();
+        let mut centrality = vec![0.0; n];
+        for (s, c) in centrality.iter_mut().enumerate() {
+            let mut distance = vec![None; n];
+            let mut queue = VecDeque::from([s]);
+            let (mut reached, mut total) = (0, 0);
+            distance[s] = Some(0);
+            while let Some(u) = queue.pop_front() {
+                let d = distance[u].unwrap();
+                reached += 1;
+                total += d;
+                for &v in &neighbours[u] {
+                    if distance[v].is_none() {
+                        distance[v] = Some(d + 1);
+                        queue.push_back(v);
+                    }
+                }
+            }
+            if total > 0 {
+                let others = (reached - 1) as f64;
+                *c = others / total as f64 * others / (n - 1) as f64;
+            }
+        }
+        keys.into_iter().zip(centrality).collect()
+    }
+
+    /// Compute the betweenness centrality of each node using Brandes'
+    /// algorithm. It's the fraction of shortest paths between other pairs
+    /// of nodes that pass through the node, normalized by the amount of
+    /// such pairs. Runs in `O(V E)` time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (char)
+    ///     ('A') => ['B']
+    ///     ('B') => ['C']
+    ///     ('C') => []
+    /// ];
+    ///
+    /// let centrality = g.betweenness_centrality();
+    ///
+    /// assert!(centrality[&'A'] == 0.0);
+    /// assert!(centrality[&'B'] == 0.5);
+    /// ```
+    pub fn betweenness_centrality(&self) -> HashMap<K, f64> {
+        let (keys, neighbours) = self.index_neighbours();
+        let n = keys.len();
+        let mut centrality = brandes(&neighbours);
+        if n > 2 {
+            let scale = 1.0 / ((n - 1) * (n - 2)) as f64;
+            centrality.iter_mut().for_each(|c| *c *= scale);
+        }
+        keys.into_iter().zip(centrality).collect()
+    }
+
+    /// Compute the eigenvector centrality of each node by power iteration.
+    /// A node is central if it's pointed to by other central nodes. The
+    /// result is normalized to unit length. Returns `None` if the iteration
+    /// doesn't converge to the given tolerance in `max_iterations`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (char)
+    ///     ('A') => ['B', 'C']
+    ///     ('B') => ['A', 'C']
+    ///     ('C') => ['A']
+    /// ];
+    ///
+    /// let centrality = g.eigenvector_centrality(1e-9, 1000).unwrap();
+    ///
+    /// assert!(centrality[&'A'] > centrality[&'B']);
+    /// assert!(centrality[&'C'] > centrality[&'B']);
+    /// ```
+    pub fn eigenvector_centrality(
+        &self,
+        tolerance: f64,
+        max_iterations: usize,
+    ) -> Option<HashMap<K, f64>> {
+        let (keys, neighbours) = self.index_neighbours();
+        let n = keys.len();
+        if n == 0 {
+            return Some(HashMap::default());
+        }
+        let mut x = vec![1.0 / n as f64; n];
+        for _ in 0..max_iterations {
+            // Iterating with `A + I` instead of `A` gives the same result
+            // but also converges on periodic graphs.
+            let mut next = x.clone();
+            for (u, list) in neighbours.iter().enumerate() {
+                for &v in list {
+                    next[v] += x[u];
+                }
+            }
+            let norm = next.iter().map(|a| a * a).sum::<f64>().sqrt();
+            next.iter_mut().for_each(|a| *a /= norm);
+            let delta = next.iter().zip(&x).map(|(a, b)| (a - b).abs()).sum::<f64>();
+            x = next;
+            if delta < n as f64 * tolerance {
+                return Some(keys.into_iter().zip(x).collect());
+            }
+        }
+        None
+    }
+}
+
+/// Brandes' algorithm for unweighted graphs. Returns the sum of pair
+/// dependencies of each node over all ordered pairs of other nodes.
+fn brandes(neighbours: &[Vec<usize>]) -> Vec<f64> {
+    let n = neighbours.len();
+    let mut centrality = vec![0.0; n];
+    for s in 0..n {
+        let mut stack = vec![];
+        let mut predecessors = vec![vec![]; n];
+        let mut paths = vec![0.0; n];
+        let mut distance = vec![None; n];
+        let mut queue = VecDeque::from([s]);
+        paths[s] = 1.0;
+        distance[s] = Some(0);
+
+        while let Some(u) = queue.pop_front() {
+            stack.push(u);
+            let d = distance[u].unwrap();
+            for &v in &neighbours[u] {
+                if distance[v].is_none() {
+                    distance[v] = Some(d + 1);
+                    queue.push_back(v);
+                }
+                if distance[v] == Some(d + 1) {
+                    paths[v] += paths[u];
+                    predecessors[v].push(u);
+                }
+            }
+        }
+
+        let mut dependency = vec![0.0; n];
+        while let Some(w) = stack.pop() {
+            for &v in &predecessors[w] {
+                dependency[v] += paths[v] / paths[w] * (1.0 + dependency[w]);
+            }
+            if w != s {
+                centrality[w] += dependency[w];
+            }
+        }
+    }
+    centrality
+}
@@ -0,0 +1,173 @@
+use super::*;
+
+/// Find the strongly connected components of a graph with `n` nodes and
+/// the given edges between node indices using Tarjan's algorithm. Runs in
+/// linear time and without recursion. Components are lists of node indices
+/// returned in reverse topological order, if there is an edge from one
+/// component to another, the target component comes first.
+pub(super) fn tarjan(n: usize, endpoints: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    // Outbound neighbours of each node in compressed sparse row form.
+    let mut offsets = vec![0; n + 1];
+    for &(u, _) in endpoints {
+        offsets[u + 1] += 1;
+    }
+    for u in 0..n {
+        offsets[u + 1] += offsets[u];
+    }
+    let mut targets = vec![0; endpoints.len()];
+    let mut fill = offsets.clone();
+    for &(u, v) in endpoints {
+        targets[fill[u]] = v;
+        fill[u] += 1;
+    }
+
+    const UNVISITED: usize = usize::MAX;
+    let mut discovered = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = vec![];
+    let mut components = vec![];
+    let mut count = 0;
+
+    for root in 0..n {
+        if discovered[root] != UNVISITED {
+            continue;
+        }
+        // Each call holds a node and the position of its next edge.
+        let mut calls = vec![(root, offsets[root])];
+        discovered[root] = count;
+        low[root] = count;
+        count += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(&(u, position)) = calls.last() {
+            if position < offsets[u + 1] {
+                calls.last_mut().unwrap().1 += 1;
+                let v = targets[position];
+                if discovered[v] == UNVISITED {
+                    discovered[v] = count;
+                    low[v] = count;
+                    count += 1;
+                    stack.push(v);
+                    on_stack[v] = true;
+                    calls.push((v, offsets[v]));
+                } else if on_stack[v] {
+                    low[u] = low[u].min(discovered[v]);
+                }
+                continue;
+            }
+            calls.pop();
+            if let Some(&(parent, _)) = calls.last() {
+                low[parent] = low[parent].min(low[u]);
+            }
+            if low[u] == discovered[u] {
+                let mut component = vec![];
+                while let Some(w) = stack.pop() {
+                    on_stack[w] = false;
+                    component.push(w);
+                    if w == u {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+impl<K, N, E> Graph<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    /// Find the strongly connected components of the graph using Tarjan's
+    /// algorithm. Runs in linear time and without recursion. Components are
+    /// returned in reverse topological order, if there is an edge from one
+    /// component to another, the target component comes first. Edges
+    /// pointing to nodes outside of the graph are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (usize)
+    ///     (0) => [1]
+    ///     (1) => [2]
+    ///     (2) => [0, 3]
+    ///     (3) => [4]
+    ///     (4) => [3]
+    /// ];
+    ///
+    /// let scc = g.tarjan_scc();
+    ///
+    /// assert!(scc.len() == 2);
+    /// assert!(scc[0].len() == 2);
+    /// assert!(scc[1].len() == 3);
+    /// ```
+    pub fn tarjan_scc(&self) -> Vec<Vec<Node<K, N, E>>> {
+        let (_, _, endpoints) = self.index_edges();
+        let nodes = self.nodes.values().collect::<Vec<_>>();
+        tarjan(nodes.len(), &endpoints)
+            .into_iter()
+            .map(|component| component.into_iter().map(|u| nodes[u].clone()).collect())
+            .collect()
+    }
+
+    /// Collapse every strongly connected component of the graph into a single
+    /// node. The resulting graph is always acyclic. Each node is keyed by the
+    /// index of its component in the order returned by `tarjan_scc` and holds
+    /// the members of the component as its value. Components are connected
+    /// by at most one edge, edges within a component are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (char)
+    ///     ('A') => ['B']
+    ///     ('B') => ['A', 'C', 'D']
+    ///     ('C') => ['D']
+    ///     ('D') => ['C']
+    /// ];
+    ///
+    /// let dag = g.condensation();
+    ///
+    /// assert!(dag.len() == 2);
+    /// assert!(dag[0].value().contains(&g['C']));
+    /// assert!(dag[1].value().contains(&g['A']));
+    /// assert!(dag[1].is_connected(dag[0].key()));
+    /// assert!(!dag[0].is_connected(dag[1].key()));
+    /// assert!(dag[1].out_degree() == 1);
+    /// ```
+    pub fn condensation(&self) -> Graph<usize, Vec<Node<K, N, E>>, ()> {
+        let components = self.tarjan_scc();
+        let mut component_of = HashMap::default();
+        let mut dag = Graph::with_capacity(components.len());
+
+        for (i, component) in components.into_iter().enumerate() {
+            for node in &component {
+                component_of.insert(node.key().clone(), i);
+            }
+            dag.insert(Node::new(i, component));
+        }
+        let mut connected = HashSet::default();
+        for (key, node) in self.iter() {
+            let u = component_of[key];
+            for Edge(_, v, _) in node.iter_out() {
+                if let Some(&v) = component_of.get(v.key()) {
+                    if u != v && connected.insert((u, v)) {
+                        dag[u].connect(&dag[v], ());
+                    }
+                }
+            }
+        }
+        dag
+    }
+}
@@ -0,0 +1,229 @@
+use super::*;
+
+/// A node on the search stack of `Cycles` together with the position of the
+/// next successor to visit and whether a cycle was found through it.
+struct Frame {
+    node: usize,
+    position: usize,
+    found: bool,
+}
+
+/// Iterator over the elementary cycles of a graph using Johnson's algorithm.
+/// Created with `Graph::cycles`. An elementary cycle visits each of its
+/// nodes once. Parallel edges are treated as a single edge, the first of
+/// them is used in the path.
+pub struct Cycles<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    edges: Vec<Edge<K, N, E>>,
+    successors: Vec<Vec<(usize, usize)>>,
+    predecessors: Vec<Vec<usize>>,
+    max_length: Option<usize>,
+    start: usize,
+    component: Vec<bool>,
+    blocked: Vec<bool>,
+    blocks: Vec<HashSet<usize>>,
+    stack: Vec<Frame>,
+    path: Vec<usize>,
+}
+
+impl<K, N, E> Cycles<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    fn new(graph: &Graph<K, N, E>) -> Self {
+        let (index, edges, endpoints) = graph.index_edges();
+        let mut successors: Vec<Vec<(usize, usize)>> = vec![vec![]; index.len()];
+        let mut predecessors = vec![vec![]; index.len()];
+        for (i, &(u, v)) in endpoints.iter().enumerate() {
+            if successors[u].iter().all(|&(w, _)| w != v) {
+                successors[u].push((v, i));
+                predecessors[v].push(u);
+            }
+        }
+        Cycles {
+            edges,
+            successors,
+            predecessors,
+            max_length: None,
+            start: 0,
+            component: vec![false; index.len()],
+            blocked: vec![false; index.len()],
+            blocks: vec![HashSet::default(); index.len()],
+            stack: vec![],
+            path: vec![],
+        }
+    }
+
+    /// Only yield cycles with at most `max_length` edges. The search does not
+    /// follow paths longer than that which keeps it usable on large graphs.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Nodes reachable from `from` through nodes not below the start node.
+    fn reach(&self, from: usize, adjacent: impl Fn(usize) -> Vec<usize>) -> Vec<bool> {
+        let mut seen = vec![false; self.component.len()];
+        let mut queue = vec![from];
+        seen[from] = true;
+        while let Some(u) = queue.pop() {
+            for v in adjacent(u) {
+                if v >= self.start && !seen[v] {
+                    seen[v] = true;
+                    queue.push(v);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Move on to the next start node and restrict the search to its strongly
+    /// connected component in the subgraph of the nodes not below it.
+    fn advance(&mut self) {
+        let s = self.start;
+        let forward = self.reach(s, |u| self.successors[u].iter().map(|&(v, _)| v).collect());
+        let backward = self.reach(s, |u| self.predecessors[u].clone());
+        for u in 0..self.component.len() {
+            self.component[u] = forward[u] && backward[u];
+            self.blocked[u] = false;
+            self.blocks[u].clear();
+        }
+        self.blocked[s] = true;
+        self.stack.push(Frame {
+            node: s,
+            position: 0,
+            found: false,
+        });
+    }
+
+    /// Unblock `node` and every node waiting on it.
+    fn unblock(&mut self, node: usize) {
+        let mut queue = vec![node];
+        while let Some(u) = queue.pop() {
+            if self.blocked[u] {
+                self.blocked[u] = false;
+                queue.extend(self.blocks[u].drain());
+            }
+        }
+    }
+
+    fn closed_path(&self, last: usize) -> Path<K, N, E> {
+        Path {
+            edges: self
+                .path
+                .iter()
+                .chain(Some(&last))
+                .map(|&i| self.edges[i].clone())
+                .collect(),
+        }
+    }
+}
+
+impl<K, N, E> Iterator for Cycles<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    type Item = Path<K, N, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let max_length = self.max_length.unwrap_or(usize::MAX);
+        loop {
+            let Some(frame) = self.stack.last_mut() else {
+                if self.start == self.component.len() {
+                    return None;
+                }
+                self.advance();
+                self.start += 1;
+                continue;
+            };
+            let (u, position) = (frame.node, frame.position);
+            let s = self.stack[0].node;
+
+            if let Some(&(v, edge)) = self.successors[u].get(position) {
+                self.stack.last_mut().unwrap().position += 1;
+                if !self.component[v] {
+                    continue;
+                }
+                if v == s {
+                    if self.path.len() < max_length {
+                        self.stack.last_mut().unwrap().found = true;
+                        return Some(self.closed_path(edge));
+                    }
+                } else if !self.blocked[v] && self.path.len() + 2 <= max_length {
+                    self.blocked[v] = true;
+                    self.path.push(edge);
+                    self.stack.push(Frame {
+                        node: v,
+                        position: 0,
+                        found: false,
+                    });
+                }
+                continue;
+            }
+
+            // All successors of `u` are done. Without a length bound a node
+            // through which no cycle was found stays blocked until one of its
+            // successors is unblocked. With a bound the node may still lie on
+            // a shorter cycle later so it is always unblocked.
+            let frame = self.stack.pop().unwrap();
+            self.path.pop();
+            if frame.found || self.max_length.is_some() {
+                self.unblock(u);
+            } else {
+                for &(v, _) in &self.successors[u] {
+                    if self.component[v] {
+                        self.blocks[v].insert(u);
+                    }
+                }
+            }
+            if let Some(parent) = self.stack.last_mut() {
+                parent.found |= frame.found;
+            }
+        }
+    }
+}
+
+impl<K, N, E> Graph<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    /// Iterate over every elementary cycle of the graph using Johnson's
+    /// algorithm. Each cycle is returned as a path that starts and ends at
+    /// the same node. Runs in `O((V + E)(C + 1))` time for `C` cycles. Use
+    /// `max_length` to bound the number of edges in a cycle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (char)
+    ///     ('A') => ['B']
+    ///     ('B') => ['A', 'C']
+    ///     ('C') => ['A', 'C']
+    /// ];
+    ///
+    /// let cycles = g.cycles().collect::<Vec<_>>();
+    ///
+    /// assert!(cycles.len() == 3);
+    /// assert!(cycles.iter().all(|c| c.first_edge().unwrap().source() == c.last_node().unwrap()));
+    ///
+    /// let short = g.cycles().max_length(2).collect::<Vec<_>>();
+    ///
+    /// assert!(short.len() == 2);
+    /// ```
+    pub fn cycles(&self) -> Cycles<K, N, E> {
+        Cycles::new(self)
+    }
+}
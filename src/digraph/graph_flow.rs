@@ -0,0 +1,385 @@
+use super::*;
+use std::{
+    collections::VecDeque,
+    ops::{Add, Sub},
+};
+
+/// Result of a maximum flow computation. Holds the value of the flow, the
+/// flow through every edge and the minimum cut separating the source from
+/// the sink.
+pub struct MaxFlow<K, N, E, W>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    value: W,
+    flows: Vec<(Edge<K, N, E>, W)>,
+    source_side: Vec<Node<K, N, E>>,
+    sink_side: Vec<Node<K, N, E>>,
+}
+
+impl<K, N, E, W> MaxFlow<K, N, E, W>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+    W: Copy + Add<Output = W>,
+{
+    /// Returns the total amount of flow from the source to the sink.
+    pub fn value(&self) -> W {
+        self.value
+    }
+
+    /// Returns the flow from `source` to `target` summed over all edges
+    /// between them or `None` if there is no such edge.
+    pub fn flow(&self, source: &K, target: &K) -> Option<W> {
+        self.flows
+            .iter()
+            .filter(|(Edge(u, v, _), _)| u.key() == source && v.key() == target)
+            .map(|(_, w)| *w)
+            .reduce(|a, b| a + b)
+    }
+
+    /// Returns every edge of the graph together with the flow through it.
+    pub fn flows(&self) -> &[(Edge<K, N, E>, W)] {
+        &self.flows
+    }
+
+    /// Returns the nodes on the source side of the minimum cut, the nodes
+    /// still reachable from the source in the residual graph.
+    pub fn source_side(&self) -> &[Node<K, N, E>] {
+        &self.source_side
+    }
+
+    /// Returns the nodes on the sink side of the minimum cut.
+    pub fn sink_side(&self) -> &[Node<K, N, E>] {
+        &self.sink_side
+    }
+
+    /// Returns the edges of the minimum cut. These are the edges leading
+    /// from the source side to the sink side, all of them are saturated and
+    /// their capacities add up to the value of the flow.
+    pub fn cut_edges(&self) -> Vec<Edge<K, N, E>> {
+        let source_side = self
+            .source_side
+            .iter()
+            .map(|node| node.key())
+            .collect::<HashSet<_>>();
+        self.flows
+            .iter()
+            .map(|(edge, _)| edge)
+            .filter(|Edge(u, v, _)| source_side.contains(u.key()) && !source_side.contains(v.key()))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Residual network of the graph. Every edge `i` of the graph is stored as a
+/// forward arc `2 * i` and a backward arc `2 * i + 1` so that the reverse of
+/// an arc `a` is always `a ^ 1`.
+struct Network<W> {
+    arcs: Vec<Vec<usize>>,
+    target: Vec<usize>,
+    residual: Vec<W>,
+    source: usize,
+    sink: usize,
+}
+
+impl<W> Network<W>
+where
+    W: Copy + PartialOrd + Default + Add<Output = W> + Sub<Output = W>,
+{
+    fn new(
+        len: usize,
+        endpoints: &[(usize, usize)],
+        capacities: Vec<W>,
+        source: usize,
+        sink: usize,
+    ) -> Self {
+        let mut network = Network {
+            arcs: vec![vec![]; len],
+            target: Vec::with_capacity(endpoints.len() * 2),
+            residual: Vec::with_capacity(endpoints.len() * 2),
+            source,
+            sink,
+        };
+        for (&(u, v), c) in endpoints.iter().zip(capacities) {
+            network.arcs[u].push(network.target.len());
+            network.target.push(v);
+            network.residual.push(c);
+            network.arcs[v].push(network.target.len());
+            network.target.push(u);
+            network.residual.push(W::default());
+        }
+        network
+    }
+
+    fn push(&mut self, arc: usize, amount: W) {
+        self.residual[arc] = self.residual[arc] - amount;
+        self.residual[arc ^ 1] = self.residual[arc ^ 1] + amount;
+    }
+
+    /// Breadth-first search over the arcs with residual capacity left.
+    /// Returns the distance of each node from the source and the arc through
+    /// which it was reached.
+    fn levels(&self) -> (Vec<Option<usize>>, Vec<Option<usize>>) {
+        let mut level = vec![None; self.arcs.len()];
+        let mut via = vec![None; self.arcs.len()];
+        let mut queue = VecDeque::new();
+
+        level[self.source] = Some(0);
+        queue.push_back(self.source);
+        while let Some(u) = queue.pop_front() {
+            for &a in &self.arcs[u] {
+                let v = self.target[a];
+                if level[v].is_none() && self.residual[a] > W::default() {
+                    level[v] = level[u].map(|l| l + 1);
+                    via[v] = Some(a);
+                    queue.push_back(v);
+                }
+            }
+        }
+        (level, via)
+    }
+
+    /// Augments along shortest paths one at a time until the sink can no
+    /// longer be reached.
+    fn edmonds_karp(&mut self) -> W {
+        let mut total = W::default();
+        loop {
+            let (level, via) = self.levels();
+            if level[self.sink].is_none() {
+                return total;
+            }
+            let mut path = vec![];
+            let mut v = self.sink;
+            while let Some(a) = via[v] {
+                path.push(a);
+                v = self.target[a ^ 1];
+            }
+            let amount = path
+                .iter()
+                .map(|&a| self.residual[a])
+                .reduce(|a, b| if b < a { b } else { a })
+                .unwrap();
+            for a in path {
+                self.push(a, amount);
+            }
+            total = total + amount;
+        }
+    }
+
+    /// Builds a level graph with a breadth-first search and saturates it
+    /// with a blocking flow before building the next one.
+    fn dinic(&mut self) -> W {
+        let mut total = W::default();
+        loop {
+            let (level, _) = self.levels();
+            if level[self.sink].is_none() {
+                return total;
+            }
+            let mut next = vec![0; self.arcs.len()];
+            while let Some(amount) = self.augment(&level, &mut next) {
+                total = total + amount;
+            }
+        }
+    }
+
+    /// Finds a path to the sink in the level graph and pushes as much flow
+    /// along it as possible. The path is kept on an explicit stack of arcs so
+    /// that long paths don't overflow the call stack. Arcs that lead to dead
+    /// ends are skipped for the rest of the phase.
+    fn augment(&mut self, level: &[Option<usize>], next: &mut [usize]) -> Option<W> {
+        let mut path: Vec<usize> = vec![];
+        let mut u = self.source;
+        loop {
+            if u == self.sink {
+                let amount = path
+                    .iter()
+                    .map(|&a| self.residual[a])
+                    .reduce(|a, b| if b < a { b } else { a })
+                    .unwrap();
+                for a in path {
+                    self.push(a, amount);
+                }
+                return Some(amount);
+            }
+            let mut advanced = false;
+            while next[u] < self.arcs[u].len() {
+                let a = self.arcs[u][next[u]];
+                let v = self.target[a];
+                if self.residual[a] > W::default() && level[v] == level[u].map(|l| l + 1) {
+                    path.push(a);
+                    u = v;
+                    advanced = true;
+                    break;
+                }
+                next[u] += 1;
+            }
+            if !advanced {
+                // Retreat from the dead end and skip the arc leading to it.
+                let a = path.pop()?;
+                u = self.target[a ^ 1];
+                next[u] += 1;
+            }
+        }
+    }
+}
+
+impl<K, N, E> Graph<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    /// Compute the maximum flow from `source` to `sink`. The capacity of each
+    /// edge is computed from the edge value by `capacity`. Returns `None` if
+    /// either node is not in the graph or they are the same node. Uses
+    /// Dinic's algorithm.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (usize) => [u64]
+    ///     (0) => [ (1, 16), (2, 13) ]
+    ///     (1) => [ (2, 10), (3, 12) ]
+    ///     (2) => [ (1, 4), (4, 14) ]
+    ///     (3) => [ (2, 9), (5, 20) ]
+    ///     (4) => [ (3, 7), (5, 4) ]
+    ///     (5) => []
+    /// ];
+    ///
+    /// let flow = g.max_flow(&0, &5, &|e| *e).unwrap();
+    ///
+    /// assert!(flow.value() == 23);
+    /// assert!(flow.flow(&3, &5) == Some(19));
+    ///
+    /// let cut = flow.cut_edges();
+    ///
+    /// assert!(cut.iter().map(|e| e.value()).sum::<u64>() == 23);
+    /// ```
+    pub fn max_flow<W>(
+        &self,
+        source: &K,
+        sink: &K,
+        capacity: &dyn Fn(&E) -> W,
+    ) -> Option<MaxFlow<K, N, E, W>>
+    where
+        W: Copy + PartialOrd + Default + Add<Output = W> + Sub<Output = W>,
+    {
+        self.dinic(source, sink, capacity)
+    }
+
+    /// Compute the maximum flow from `source` to `sink` using the
+    /// Edmonds-Karp algorithm, augmenting along a shortest path at a time.
+    /// Runs in `O(V E^2)` time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (char) => [u64]
+    ///     ('S') => [ ('A', 3), ('B', 2) ]
+    ///     ('A') => [ ('B', 1), ('T', 2) ]
+    ///     ('B') => [ ('T', 3) ]
+    ///     ('T') => []
+    /// ];
+    ///
+    /// let flow = g.edmonds_karp(&'S', &'T', &|e| *e).unwrap();
+    ///
+    /// assert!(flow.value() == 5);
+    /// assert!(flow.source_side() == [g['S'].clone()]);
+    /// ```
+    pub fn edmonds_karp<W>(
+        &self,
+        source: &K,
+        sink: &K,
+        capacity: &dyn Fn(&E) -> W,
+    ) -> Option<MaxFlow<K, N, E, W>>
+    where
+        W: Copy + PartialOrd + Default + Add<Output = W> + Sub<Output = W>,
+    {
+        self.flow_network(source, sink, capacity, Network::edmonds_karp)
+    }
+
+    /// Compute the maximum flow from `source` to `sink` using Dinic's
+    /// algorithm, saturating a level graph of shortest paths at a time. Runs
+    /// in `O(V^2 E)` time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (char) => [f64]
+    ///     ('S') => [ ('A', 1.5), ('B', 0.5) ]
+    ///     ('A') => [ ('T', 1.0) ]
+    ///     ('B') => [ ('T', 2.0) ]
+    ///     ('T') => []
+    /// ];
+    ///
+    /// let flow = g.dinic(&'S', &'T', &|e| *e).unwrap();
+    ///
+    /// assert!(flow.value() == 1.5);
+    /// assert!(flow.sink_side().len() == 2);
+    /// ```
+    pub fn dinic<W>(
+        &self,
+        source: &K,
+        sink: &K,
+        capacity: &dyn Fn(&E) -> W,
+    ) -> Option<MaxFlow<K, N, E, W>>
+    where
+        W: Copy + PartialOrd + Default + Add<Output = W> + Sub<Output = W>,
+    {
+        self.flow_network(source, sink, capacity, Network::dinic)
+    }
+
+    fn flow_network<W>(
+        &self,
+        source: &K,
+        sink: &K,
+        capacity: &dyn Fn(&E) -> W,
+        solve: fn(&mut Network<W>) -> W,
+    ) -> Option<MaxFlow<K, N, E, W>>
+    where
+        W: Copy + PartialOrd + Default + Add<Output = W> + Sub<Output = W>,
+    {
+        let (index, edges, endpoints) = self.index_edges();
+        let (s, t) = (*index.get(source)?, *index.get(sink)?);
+        if s == t {
+            return None;
+        }
+        let capacities = edges.iter().map(|e| capacity(e.value())).collect();
+        let mut network = Network::new(index.len(), &endpoints, capacities, s, t);
+        let value = solve(&mut network);
+
+        let (level, _) = network.levels();
+        let mut source_side = vec![];
+        let mut sink_side = vec![];
+        for (key, &i) in index.iter() {
+            match level[i] {
+                Some(_) => source_side.push(self.nodes[key].clone()),
+                None => sink_side.push(self.nodes[key].clone()),
+            }
+        }
+        let flows = edges
+            .into_iter()
+            .enumerate()
+            .map(|(i, edge)| (edge, network.residual[2 * i + 1]))
+            .collect();
+        Some(MaxFlow {
+            value,
+            flows,
+            source_side,
+            sink_side,
+        })
+    }
+}
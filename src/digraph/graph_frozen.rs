@@ -0,0 +1,343 @@
+use super::*;
+use crate::visit::Visit;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+    ops::Add,
+};
+
+/// An immutable snapshot of a `Graph` in compressed sparse row form. Nodes
+/// are addressed by dense `u32` indices and the outbound edges of each node
+/// are stored next to each other in flat arrays, so traversals need no
+/// reference counting, borrowing or cloning of edges. A key to index map
+/// translates between node keys and indices.
+///
+/// The snapshot does not change when the graph it was frozen from does.
+/// Call `thaw` to turn it back into a `Graph`.
+///
+/// # Example
+///
+/// ```
+/// use gdsl::digraph::*;
+/// use gdsl::*;
+///
+/// let g = digraph![
+///     (char) => [u64]
+///     ('A') => [('B', 1), ('C', 4)]
+///     ('B') => [('C', 2)]
+///     ('C') => [('A', 1)]
+///     ('D') => []
+/// ];
+///
+/// let frozen = g.freeze();
+/// let a = frozen.index(&'A').unwrap();
+/// let c = frozen.index(&'C').unwrap();
+///
+/// let (dist, path) = frozen.shortest_path(a, c, &|w| *w).unwrap();
+/// let path = path.iter().map(|&u| *frozen.key(u)).collect::<Vec<_>>();
+///
+/// assert!(dist == 3);
+/// assert!(path == vec!['A', 'B', 'C']);
+/// assert!(frozen.scc().len() == 2);
+/// ```
+pub struct FrozenGraph<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    keys: Vec<K>,
+    values: Vec<N>,
+    index: HashMap<K, u32>,
+    offsets: Vec<usize>,
+    targets: Vec<u32>,
+    edge_keys: Vec<usize>,
+    edge_values: Vec<E>,
+}
+
+impl<K, N, E> Graph<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    /// Take an immutable compressed sparse row snapshot of the graph for
+    /// fast read-only analysis. Edges to nodes that are not in the graph
+    /// are left out. Parallel edges are kept together with their keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph has more than `u32::MAX` nodes.
+    pub fn freeze(&self) -> FrozenGraph<K, N, E> {
+        let nodes = self.to_vec();
+        assert!(
+            u32::try_from(nodes.len()).is_ok(),
+            "A frozen graph can hold at most u32::MAX nodes."
+        );
+        let index: HashMap<K, u32> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.key().clone(), i as u32))
+            .collect();
+
+        let mut offsets = Vec::with_capacity(nodes.len() + 1);
+        let mut targets = vec![];
+        let mut edge_keys = vec![];
+        let mut edge_values = vec![];
+        offsets.push(0);
+        for node in &nodes {
+            for (v, key, e) in node.outbound_keyed() {
+                if let Some(&v) = index.get(v.key()) {
+                    targets.push(v);
+                    edge_keys.push(key);
+                    edge_values.push(e);
+                }
+            }
+            offsets.push(targets.len());
+        }
+
+        FrozenGraph {
+            keys: nodes.iter().map(|node| node.key().clone()).collect(),
+            values: nodes.iter().map(|node| node.value().clone()).collect(),
+            index,
+            offsets,
+            targets,
+            edge_keys,
+            edge_values,
+        }
+    }
+}
+
+impl<K, N, E> FrozenGraph<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    /// Returns the amount of nodes in the graph.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns true if the graph contains no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the amount of edges in the graph.
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Returns the index of the node with the given key.
+    pub fn index(&self, key: &K) -> Option<u32> {
+        self.index.get(key).copied()
+    }
+
+    /// Returns the key of the node at the given index.
+    pub fn key(&self, u: u32) -> &K {
+        &self.keys[u as usize]
+    }
+
+    /// Returns the value of the node at the given index.
+    pub fn value(&self, u: u32) -> &N {
+        &self.values[u as usize]
+    }
+
+    /// Returns the amount of outbound edges of the node at the given index.
+    pub fn out_degree(&self, u: u32) -> usize {
+        self.successors(u).len()
+    }
+
+    /// Returns the targets of the outbound edges of the node at the given
+    /// index in the order the edges were created.
+    pub fn successors(&self, u: u32) -> &[u32] {
+        &self.targets[self.range(u)]
+    }
+
+    /// Returns an iterator over the targets and values of the outbound
+    /// edges of the node at the given index.
+    pub fn edges(&self, u: u32) -> impl Iterator<Item = (u32, &E)> + '_ {
+        let range = self.range(u);
+        self.targets[range.clone()]
+            .iter()
+            .copied()
+            .zip(&self.edge_values[range])
+    }
+
+    fn range(&self, u: u32) -> std::ops::Range<usize> {
+        self.offsets[u as usize]..self.offsets[u as usize + 1]
+    }
+
+    /// Returns the nodes reachable from the root in breadth-first order
+    /// starting with the root.
+    pub fn bfs(&self, root: u32) -> Vec<u32> {
+        let mut visited = vec![false; self.len()];
+        let mut queue = VecDeque::from([root]);
+        let mut order = vec![];
+        visited[root as usize] = true;
+        while let Some(u) = queue.pop_front() {
+            order.push(u);
+            for &v in self.successors(u) {
+                if !visited[v as usize] {
+                    visited[v as usize] = true;
+                    queue.push_back(v);
+                }
+            }
+        }
+        order
+    }
+
+    /// Returns the nodes reachable from the root in depth-first pre-order
+    /// starting with the root.
+    pub fn dfs(&self, root: u32) -> Vec<u32> {
+        let mut visited = vec![false; self.len()];
+        let mut stack = vec![(root, self.offsets[root as usize])];
+        let mut order = vec![root];
+        visited[root as usize] = true;
+        while let Some(&(u, position)) = stack.last() {
+            if position == self.offsets[u as usize + 1] {
+                stack.pop();
+                continue;
+            }
+            stack.last_mut().unwrap().1 += 1;
+            let v = self.targets[position];
+            if !visited[v as usize] {
+                visited[v as usize] = true;
+                order.push(v);
+                stack.push((v, self.offsets[v as usize]));
+            }
+        }
+        order
+    }
+
+    /// Returns the nodes reachable from the root in priority-first order
+    /// starting with the root. The next node is always the one with the
+    /// smallest value among the nodes adjacent to the nodes visited so far.
+    /// Ties are broken by the smaller index.
+    pub fn pfs(&self, root: u32) -> Vec<u32>
+    where
+        N: Ord,
+    {
+        let mut visited = vec![false; self.len()];
+        let mut queue = BinaryHeap::from([Reverse((self.value(root), root))]);
+        let mut order = vec![];
+        while let Some(Reverse((_, u))) = queue.pop() {
+            if visited[u as usize] {
+                continue;
+            }
+            visited[u as usize] = true;
+            order.push(u);
+            for &v in self.successors(u) {
+                if !visited[v as usize] {
+                    queue.push(Reverse((self.value(v), v)));
+                }
+            }
+        }
+        order
+    }
+
+    /// Find the strongly connected components of the graph with Tarjan's
+    /// algorithm. Each component is a list of node indices. Components are
+    /// returned in reverse topological order of the condensed graph.
+    pub fn scc(&self) -> Vec<Vec<u32>> {
+        let mut endpoints = Vec::with_capacity(self.edge_count());
+        for u in 0..self.len() {
+            for &v in self.successors(u as u32) {
+                endpoints.push((u, v as usize));
+            }
+        }
+        graph_components::tarjan(self.len(), &endpoints)
+            .into_iter()
+            .map(|component| component.into_iter().map(|u| u as u32).collect())
+            .collect()
+    }
+
+    /// Find the shortest paths from the source to every node with Dijkstra's
+    /// algorithm. Returns the distance of each node and the node it is
+    /// reached from, indexed by node. Unreachable nodes have neither. The
+    /// weights must not be negative.
+    pub fn shortest_paths<W>(
+        &self,
+        source: u32,
+        weight: &dyn Fn(&E) -> W,
+    ) -> (Vec<Option<W>>, Vec<Option<u32>>)
+    where
+        W: Copy + PartialOrd + Default + Add<Output = W>,
+    {
+        let mut distances = vec![None; self.len()];
+        let mut predecessors = vec![None; self.len()];
+        let mut queue = BinaryHeap::new();
+        distances[source as usize] = Some(W::default());
+        queue.push(Visit(W::default(), source as usize));
+        while let Some(Visit(dist, u)) = queue.pop() {
+            if distances[u].is_some_and(|best| best < dist) {
+                continue;
+            }
+            for (v, e) in self.edges(u as u32) {
+                let alt = dist + weight(e);
+                if distances[v as usize].is_none_or(|best| alt < best) {
+                    distances[v as usize] = Some(alt);
+                    predecessors[v as usize] = Some(u as u32);
+                    queue.push(Visit(alt, v as usize));
+                }
+            }
+        }
+        predecessors[source as usize] = None;
+        (distances, predecessors)
+    }
+
+    /// Find a shortest path from the source to the target. Returns the
+    /// length of the path and the indices of its nodes from the source to
+    /// the target, or `None` if the target is not reachable. Use `key` to
+    /// translate the indices back to node keys.
+    pub fn shortest_path<W>(
+        &self,
+        source: u32,
+        target: u32,
+        weight: &dyn Fn(&E) -> W,
+    ) -> Option<(W, Vec<u32>)>
+    where
+        W: Copy + PartialOrd + Default + Add<Output = W>,
+    {
+        let (distances, predecessors) = self.shortest_paths(source, weight);
+        let dist = distances[target as usize]?;
+        let mut path = vec![target];
+        while let Some(u) = predecessors[path[path.len() - 1] as usize] {
+            path.push(u);
+        }
+        path.reverse();
+        Some((dist, path))
+    }
+
+    /// Build a new `Graph` from the snapshot. The edges keep their keys,
+    /// values and order.
+    pub fn thaw(&self) -> Graph<K, N, E> {
+        let nodes = self
+            .keys
+            .iter()
+            .zip(&self.values)
+            .map(|(key, value)| Node::new(key.clone(), value.clone()))
+            .collect::<Vec<_>>();
+        for (u, node) in nodes.iter().enumerate() {
+            for i in self.range(u as u32) {
+                let v = &nodes[self.targets[i] as usize];
+                let value = self.edge_values[i].clone();
+                // The key `0` is reserved for edges made with `connect`,
+                // which `connect_keyed` rejects. Any other key is unique
+                // among the edges between two nodes.
+                match self.edge_keys[i] {
+                    0 => node.connect(v, value),
+                    key => node
+                        .connect_keyed(v, key, value)
+                        .expect("keys other than 0 are unique between two nodes"),
+                }
+            }
+        }
+        let mut g = Graph::new();
+        for node in nodes {
+            g.insert(node);
+        }
+        g
+    }
+}
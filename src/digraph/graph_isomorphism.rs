@@ -0,0 +1,487 @@
+use super::*;
+
+/// Dense index of a graph used by the matcher. Edges are grouped by their
+/// endpoints so that parallel edges are compared together.
+struct Indexed<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    nodes: Vec<Node<K, N, E>>,
+    edges: HashMap<(usize, usize), Vec<E>>,
+    out_degree: Vec<usize>,
+    in_degree: Vec<usize>,
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl<K, N, E> Indexed<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    fn new(graph: &Graph<K, N, E>) -> Self {
+        let nodes = graph.nodes.values().cloned().collect::<Vec<_>>();
+        let index: HashMap<K, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.key().clone(), i))
+            .collect();
+        let mut edges: HashMap<(usize, usize), Vec<E>> = HashMap::default();
+        let mut out_degree = vec![0; nodes.len()];
+        let mut in_degree = vec![0; nodes.len()];
+        let mut neighbours = vec![HashSet::default(); nodes.len()];
+        for (u, node) in nodes.iter().enumerate() {
+            for Edge(_, v, e) in node.iter_out() {
+                if let Some(&v) = index.get(v.key()) {
+                    edges.entry((u, v)).or_default().push(e);
+                    out_degree[u] += 1;
+                    in_degree[v] += 1;
+                    neighbours[u].insert(v);
+                    neighbours[v].insert(u);
+                }
+            }
+        }
+        let neighbours = neighbours
+            .into_iter()
+            .map(|set| set.into_iter().collect())
+            .collect();
+        Indexed {
+            nodes,
+            edges,
+            out_degree,
+            in_degree,
+            neighbours,
+        }
+    }
+
+    fn edges(&self, u: usize, v: usize) -> &[E] {
+        self.edges.get(&(u, v)).map_or(&[], |e| e.as_slice())
+    }
+}
+
+/// Returns true if every edge value in `a` can be paired with a different
+/// edge value in `b` so that `f` holds for each pair. Parallel edges have no
+/// order, so the pairs are found as a bipartite matching grown along
+/// augmenting paths.
+fn match_parallel<E>(a: &[E], b: &[E], f: &dyn Fn(&E, &E) -> bool) -> bool {
+    fn augment<E>(
+        i: usize,
+        a: &[E],
+        b: &[E],
+        f: &dyn Fn(&E, &E) -> bool,
+        seen: &mut [bool],
+        pair: &mut [Option<usize>],
+    ) -> bool {
+        for j in 0..b.len() {
+            if !seen[j] && f(&a[i], &b[j]) {
+                seen[j] = true;
+                let free = match pair[j] {
+                    Some(k) => augment(k, a, b, f, seen, pair),
+                    None => true,
+                };
+                if free {
+                    pair[j] = Some(i);
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    let mut pair = vec![None; b.len()];
+    (0..a.len()).all(|i| augment(i, a, b, f, &mut vec![false; b.len()], &mut pair))
+}
+
+/// Matcher between a graph and a pattern based on the VF2 algorithm. Created
+/// with `Graph::isomorphism`. By default nodes and edges match regardless
+/// of their values, use `node_match` and `edge_match` to compare them.
+pub struct Isomorphism<'a, K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    graph: &'a Graph<K, N, E>,
+    pattern: &'a Graph<K, N, E>,
+    node_match: Option<&'a dyn Fn(&N, &N) -> bool>,
+    edge_match: Option<&'a dyn Fn(&E, &E) -> bool>,
+}
+
+impl<'a, K, N, E> Isomorphism<'a, K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    pub fn new(graph: &'a Graph<K, N, E>, pattern: &'a Graph<K, N, E>) -> Self {
+        Isomorphism {
+            graph,
+            pattern,
+            node_match: None,
+            edge_match: None,
+        }
+    }
+
+    /// Only map nodes onto each other if `f(graph_value, pattern_value)`
+    /// returns true.
+    pub fn node_match(mut self, f: &'a dyn Fn(&N, &N) -> bool) -> Self {
+        self.node_match = Some(f);
+        self
+    }
+
+    /// Only map edges onto each other if `f(graph_value, pattern_value)`
+    /// returns true.
+    pub fn edge_match(mut self, f: &'a dyn Fn(&E, &E) -> bool) -> Self {
+        self.edge_match = Some(f);
+        self
+    }
+
+    /// Returns true if the graph and the pattern are isomorphic.
+    pub fn is_isomorphic(&self) -> bool {
+        self.mappings().next().is_some()
+    }
+
+    /// Returns true if the pattern is isomorphic to an induced subgraph of
+    /// the graph.
+    pub fn is_subgraph_isomorphic(&self) -> bool {
+        self.subgraph_mappings().next().is_some()
+    }
+
+    /// Iterate over the isomorphisms between the graph and the pattern.
+    pub fn mappings(&self) -> Mappings<'a, K, N, E> {
+        Mappings::new(self, false)
+    }
+
+    /// Iterate over the isomorphisms between the pattern and the induced
+    /// subgraphs of the graph. An induced subgraph contains every edge of the
+    /// graph between its nodes.
+    pub fn subgraph_mappings(&self) -> Mappings<'a, K, N, E> {
+        Mappings::new(self, true)
+    }
+}
+
+/// Iterator over the mappings found by `Isomorphism`. Each mapping maps the
+/// key of every pattern node to the key of a graph node.
+pub struct Mappings<'a, K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    graph: Indexed<K, N, E>,
+    pattern: Indexed<K, N, E>,
+    node_match: Option<&'a dyn Fn(&N, &N) -> bool>,
+    edge_match: Option<&'a dyn Fn(&E, &E) -> bool>,
+    subgraph: bool,
+    order: Vec<usize>,
+    anchor: Vec<Option<usize>>,
+    core: Vec<Option<usize>>,
+    used: Vec<bool>,
+    stack: Vec<(Vec<usize>, usize)>,
+    done: bool,
+}
+
+impl<'a, K, N, E> Mappings<'a, K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    fn new(matcher: &Isomorphism<'a, K, N, E>, subgraph: bool) -> Self {
+        let graph = Indexed::new(matcher.graph);
+        let pattern = Indexed::new(matcher.pattern);
+        let n = pattern.nodes.len();
+
+        // Pattern nodes are matched in breadth-first order starting from the
+        // node with the most neighbours in each component. Every node except
+        // the first of a component is anchored to an earlier neighbour whose
+        // image limits the candidates.
+        let mut order = vec![];
+        let mut anchor = vec![];
+        let mut seen = vec![false; n];
+        let mut roots = (0..n).collect::<Vec<_>>();
+        roots.sort_by_key(|&u| std::cmp::Reverse(pattern.neighbours[u].len()));
+        for root in roots {
+            if seen[root] {
+                continue;
+            }
+            seen[root] = true;
+            let start = order.len();
+            order.push(root);
+            anchor.push(None);
+            let mut i = start;
+            while i < order.len() {
+                let u = order[i];
+                for &v in &pattern.neighbours[u] {
+                    if !seen[v] {
+                        seen[v] = true;
+                        order.push(v);
+                        anchor.push(Some(u));
+                    }
+                }
+                i += 1;
+            }
+        }
+
+        let done = match subgraph {
+            true => n > graph.nodes.len(),
+            false => n != graph.nodes.len() || graph.edges.len() != pattern.edges.len(),
+        };
+        Mappings {
+            used: vec![false; graph.nodes.len()],
+            core: vec![None; n],
+            graph,
+            pattern,
+            node_match: matcher.node_match,
+            edge_match: matcher.edge_match,
+            subgraph,
+            order,
+            anchor,
+            stack: vec![],
+            done,
+        }
+    }
+
+    fn same_edges(&self, t: (usize, usize), p: (usize, usize)) -> bool {
+        let (te, pe) = (self.graph.edges(t.0, t.1), self.pattern.edges(p.0, p.1));
+        te.len() == pe.len() && self.edge_match.is_none_or(|f| match_parallel(te, pe, f))
+    }
+
+    /// Checks whether pattern node `p` can be mapped onto graph node `t`
+    /// given the nodes mapped so far.
+    fn feasible(&self, p: usize, t: usize) -> bool {
+        let degrees = match self.subgraph {
+            true => {
+                self.graph.out_degree[t] >= self.pattern.out_degree[p]
+                    && self.graph.in_degree[t] >= self.pattern.in_degree[p]
+            }
+            false => {
+                self.graph.out_degree[t] == self.pattern.out_degree[p]
+                    && self.graph.in_degree[t] == self.pattern.in_degree[p]
+            }
+        };
+        degrees
+            && self
+                .node_match
+                .is_none_or(|f| f(self.graph.nodes[t].value(), self.pattern.nodes[p].value()))
+            && self.same_edges((t, t), (p, p))
+            && self.order.iter().all(|&q| match self.core[q] {
+                Some(s) => self.same_edges((t, s), (p, q)) && self.same_edges((s, t), (q, p)),
+                None => true,
+            })
+    }
+
+    fn candidates(&self, depth: usize) -> Vec<usize> {
+        let p = self.order[depth];
+        let pool = match self.anchor[depth] {
+            Some(a) => self.graph.neighbours[self.core[a].unwrap()].clone(),
+            None => (0..self.graph.nodes.len()).collect(),
+        };
+        pool.into_iter()
+            .filter(|&t| !self.used[t] && self.feasible(p, t))
+            .collect()
+    }
+
+    fn mapping(&self) -> std::collections::HashMap<K, K> {
+        self.core
+            .iter()
+            .enumerate()
+            .map(|(p, t)| {
+                let t = t.unwrap();
+                (
+                    self.pattern.nodes[p].key().clone(),
+                    self.graph.nodes[t].key().clone(),
+                )
+            })
+            .collect()
+    }
+}
+
+impl<'a, K, N, E> Iterator for Mappings<'a, K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    type Item = std::collections::HashMap<K, K>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if self.order.is_empty() {
+            self.done = true;
+            return Some(std::collections::HashMap::new());
+        }
+        if self.stack.is_empty() {
+            let candidates = self.candidates(0);
+            self.stack.push((candidates, 0));
+        }
+        while !self.stack.is_empty() {
+            let depth = self.stack.len() - 1;
+            let p = self.order[depth];
+            if let Some(t) = self.core[p].take() {
+                self.used[t] = false;
+            }
+            let (candidates, position) = &mut self.stack[depth];
+            let Some(&t) = candidates.get(*position) else {
+                self.stack.pop();
+                continue;
+            };
+            *position += 1;
+            self.core[p] = Some(t);
+            self.used[t] = true;
+            if depth + 1 == self.order.len() {
+                return Some(self.mapping());
+            }
+            let candidates = self.candidates(depth + 1);
+            self.stack.push((candidates, 0));
+        }
+        self.done = true;
+        None
+    }
+}
+
+impl<K, N, E> Graph<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    /// Create a matcher between this graph and a pattern graph. The matcher
+    /// can test for isomorphism and subgraph isomorphism and iterate over
+    /// the mappings between the graphs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (usize) => [u32]
+    ///     (0) => [ (1, 1), (2, 2) ]
+    ///     (1) => [ (2, 1) ]
+    ///     (2) => [ (3, 1) ]
+    ///     (3) => []
+    /// ];
+    ///
+    /// let pattern = digraph![
+    ///     (usize) => [u32]
+    ///     (10) => [ (11, 1) ]
+    ///     (11) => [ (12, 1) ]
+    ///     (12) => []
+    /// ];
+    ///
+    /// let same = |a: &u32, b: &u32| a == b;
+    /// let matcher = g.isomorphism(&pattern).edge_match(&same);
+    ///
+    /// // 0 -> 1 -> 2 is not induced because of the edge 0 -> 2.
+    /// let mappings = matcher.subgraph_mappings().collect::<Vec<_>>();
+    ///
+    /// assert!(mappings.len() == 1);
+    /// assert!(mappings[0][&10] == 1);
+    /// assert!(mappings[0][&12] == 3);
+    /// ```
+    pub fn isomorphism<'a>(&'a self, pattern: &'a Graph<K, N, E>) -> Isomorphism<'a, K, N, E> {
+        Isomorphism::new(self, pattern)
+    }
+
+    /// Returns true if the graphs are structurally identical, there is a
+    /// bijection between their nodes that preserves the edges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let a = digraph![
+    ///     (char)
+    ///     ('A') => ['B', 'C']
+    ///     ('B') => ['C']
+    ///     ('C') => []
+    /// ];
+    ///
+    /// let b = digraph![
+    ///     (char)
+    ///     ('X') => []
+    ///     ('Y') => ['X', 'Z']
+    ///     ('Z') => ['X']
+    /// ];
+    ///
+    /// assert!(a.is_isomorphic(&b));
+    ///
+    /// b['X'].connect(&b['Y'], ());
+    ///
+    /// assert!(!a.is_isomorphic(&b));
+    /// ```
+    pub fn is_isomorphic(&self, other: &Graph<K, N, E>) -> bool {
+        self.isomorphism(other).is_isomorphic()
+    }
+
+    /// Returns true if the pattern is isomorphic to an induced subgraph of
+    /// this graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (char)
+    ///     ('A') => ['B']
+    ///     ('B') => ['C']
+    ///     ('C') => ['A', 'D']
+    ///     ('D') => []
+    /// ];
+    ///
+    /// let cycle = digraph![
+    ///     (char)
+    ///     ('x') => ['y']
+    ///     ('y') => ['z']
+    ///     ('z') => ['x']
+    /// ];
+    ///
+    /// let triangle = digraph![
+    ///     (char)
+    ///     ('x') => ['y', 'z']
+    ///     ('y') => ['z']
+    ///     ('z') => []
+    /// ];
+    ///
+    /// assert!(g.is_subgraph_isomorphic(&cycle));
+    /// assert!(!g.is_subgraph_isomorphic(&triangle));
+    /// ```
+    pub fn is_subgraph_isomorphic(&self, pattern: &Graph<K, N, E>) -> bool {
+        self.isomorphism(pattern).is_subgraph_isomorphic()
+    }
+
+    /// Iterate over the isomorphisms between the pattern and the induced
+    /// subgraphs of this graph. Each mapping maps the key of every pattern
+    /// node to the key of a node in this graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (usize)
+    ///     (0) => [1]
+    ///     (1) => [2]
+    ///     (2) => [0]
+    /// ];
+    ///
+    /// let pattern = digraph![
+    ///     (usize)
+    ///     (0) => [1]
+    ///     (1) => []
+    /// ];
+    ///
+    /// assert!(g.subgraph_mappings(&pattern).count() == 3);
+    /// ```
+    pub fn subgraph_mappings<'a>(&'a self, pattern: &'a Graph<K, N, E>) -> Mappings<'a, K, N, E> {
+        self.isomorphism(pattern).subgraph_mappings()
+    }
+}
@@ -0,0 +1,186 @@
+use super::*;
+use crate::error::Error;
+use std::fmt::Debug;
+use thiserror::Error;
+
+/// A cycle found in a graph that was required to be acyclic. It converts into
+/// `Error::Cycle` for callers that don't need the cycle.
+#[derive(Error)]
+#[error("Cycle found")]
+pub struct Cycle<K, N, E>(pub Path<K, N, E>)
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone;
+
+impl<K, N, E> Debug for Cycle<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Cycle").field(&self.0).finish()
+    }
+}
+
+impl<K, N, E> From<Cycle<K, N, E>> for Error
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    fn from(_: Cycle<K, N, E>) -> Self {
+        Error::Cycle
+    }
+}
+
+impl<K, N, E> Graph<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    /// Sort the nodes of the graph topologically so that for every edge the
+    /// source node comes before the target node. If the graph is not acyclic,
+    /// one of its cycles is returned as a `Cycle`. Edges pointing
+    /// to nodes outside of the graph are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    /// use gdsl::digraph::Cycle;
+    ///
+    /// let mut g = digraph![
+    ///     (char)
+    ///     ('A') => ['B', 'C']
+    ///     ('B') => ['D']
+    ///     ('C') => ['D']
+    ///     ('D') => []
+    /// ];
+    ///
+    /// let order = g.topological_sort().unwrap();
+    ///
+    /// assert!(order.first() == Some(&g['A']));
+    /// assert!(order.last() == Some(&g['D']));
+    ///
+    /// g['D'].connect(&g['A'], ());
+    ///
+    /// match g.topological_sort() {
+    ///     Err(Cycle(cycle)) => assert!(cycle.len() == 4),
+    ///     _ => panic!("expected a cycle"),
+    /// }
+    /// ```
+    pub fn topological_sort(&self) -> Result<Vec<Node<K, N, E>>, Cycle<K, N, E>> {
+        Ok(self.topological_layers()?.into_iter().flatten().collect())
+    }
+
+    /// Group the nodes of the graph into layers using Kahn's algorithm. The
+    /// first layer contains the nodes without inbound edges and every
+    /// following layer the nodes whose inbound edges all come from earlier
+    /// layers. The nodes within a layer don't depend on each other and can be
+    /// processed in parallel. If the graph is not acyclic, one of its cycles
+    /// is returned as a `Cycle`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (char)
+    ///     ('A') => ['C']
+    ///     ('B') => ['C', 'D']
+    ///     ('C') => ['E']
+    ///     ('D') => ['E']
+    ///     ('E') => []
+    /// ];
+    ///
+    /// let layers = g.topological_layers().unwrap();
+    ///
+    /// assert!(layers.len() == 3);
+    /// assert!(layers[0].len() == 2);
+    /// assert!(layers[1].len() == 2);
+    /// assert!(layers[2] == vec![g['E'].clone()]);
+    /// ```
+    pub fn topological_layers(&self) -> Result<Vec<Vec<Node<K, N, E>>>, Cycle<K, N, E>> {
+        let mut in_degree: HashMap<K, usize> = self.nodes.keys().map(|k| (k.clone(), 0)).collect();
+        for node in self.nodes.values() {
+            for Edge(_, v, _) in node.iter_out() {
+                if let Some(degree) = in_degree.get_mut(v.key()) {
+                    *degree += 1;
+                }
+            }
+        }
+
+        let mut layers = vec![];
+        let mut layer = self
+            .nodes
+            .values()
+            .filter(|node| in_degree[node.key()] == 0)
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut sorted = 0;
+
+        while !layer.is_empty() {
+            let mut next = vec![];
+            for node in &layer {
+                for Edge(_, v, _) in node.iter_out() {
+                    if let Some(degree) = in_degree.get_mut(v.key()) {
+                        *degree -= 1;
+                        if *degree == 0 {
+                            next.push(v);
+                        }
+                    }
+                }
+            }
+            sorted += layer.len();
+            layers.push(layer);
+            layer = next;
+        }
+
+        if sorted < self.len() {
+            return Err(Cycle(self.remaining_cycle(&in_degree)));
+        }
+        Ok(layers)
+    }
+
+    /// Every node left with a non-zero in-degree after Kahn's algorithm has a
+    /// predecessor in the graph that was left as well. Walking the
+    /// predecessors backwards must therefore end up in a cycle. Only the
+    /// outbound edges of the nodes in the graph are followed, the same edges
+    /// the in-degrees were counted from.
+    fn remaining_cycle(&self, in_degree: &HashMap<K, usize>) -> Path<K, N, E> {
+        let remaining = |key: &K| in_degree.get(key).is_some_and(|&d| d > 0);
+        let mut predecessor = HashMap::default();
+        for (_, node) in self.iter().filter(|(key, _)| remaining(key)) {
+            for edge in node.iter_out() {
+                if remaining(edge.target().key()) {
+                    predecessor
+                        .entry(edge.target().key().clone())
+                        .or_insert(edge);
+                }
+            }
+        }
+
+        let mut visited = HashSet::default();
+        let mut current = predecessor.keys().next().unwrap().clone();
+        while visited.insert(current.clone()) {
+            current = predecessor[&current].source().key().clone();
+        }
+
+        let start = current.clone();
+        let mut edges = vec![];
+        loop {
+            let edge = predecessor[&current].clone();
+            current = edge.source().key().clone();
+            edges.push(edge);
+            if current == start {
+                break;
+            }
+        }
+        edges.reverse();
+        Path { edges }
+    }
+}
@@ -0,0 +1,321 @@
+use super::*;
+use crate::visit::Visit;
+use std::{
+    collections::BinaryHeap,
+    ops::{Add, Sub},
+};
+
+/// Shortest path distances between every pair of nodes in a graph. Nodes
+/// are addressed by key and any shortest path can be reconstructed into a
+/// `Path`.
+pub struct DistanceMatrix<K, N, E, W>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    index: HashMap<K, usize>,
+    distances: Vec<Option<W>>,
+    predecessors: Vec<Option<usize>>,
+    edges: Vec<Edge<K, N, E>>,
+}
+
+impl<K, N, E, W> DistanceMatrix<K, N, E, W>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+    W: Copy,
+{
+    /// Returns the amount of nodes in the matrix.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns true if the matrix contains no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Returns the length of the shortest path from `source` to `target` or
+    /// `None` if the target is not reachable from the source.
+    pub fn distance(&self, source: &K, target: &K) -> Option<W> {
+        let (s, t) = (*self.index.get(source)?, *self.index.get(target)?);
+        self.distances[s * self.len() + t]
+    }
+
+    /// Reconstructs the shortest path from `source` to `target`. Returns
+    /// `None` if the target is not reachable from the source or is the
+    /// source itself.
+    pub fn path(&self, source: &K, target: &K) -> Option<Path<K, N, E>> {
+        let (s, mut t) = (*self.index.get(source)?, *self.index.get(target)?);
+        let mut edges = vec![];
+        while t != s {
+            let edge = &self.edges[self.predecessors[s * self.len() + t]?];
+            t = self.index[edge.source().key()];
+            edges.push(edge.clone());
+        }
+        if edges.is_empty() {
+            return None;
+        }
+        edges.reverse();
+        Some(Path { edges })
+    }
+}
+
+impl<K, N, E> Graph<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    /// Assign each node a dense index and collect the edges between nodes
+    /// in the graph. Edges pointing outside of the graph are ignored.
+    pub(super) fn index_edges(
+        &self,
+    ) -> (HashMap<K, usize>, Vec<Edge<K, N, E>>, Vec<(usize, usize)>) {
+        let index: HashMap<K, usize> = self
+            .nodes
+            .keys()
+            .enumerate()
+            .map(|(i, k)| (k.clone(), i))
+            .collect();
+        let mut edges = vec![];
+        let mut endpoints = vec![];
+        for node in self.nodes.values() {
+            for edge in node.iter_out() {
+                if let Some(&v) = index.get(edge.target().key()) {
+                    endpoints.push((index[node.key()], v));
+                    edges.push(edge);
+                }
+            }
+        }
+        (index, edges, endpoints)
+    }
+
+    /// Walk back from `start` along the predecessor edges given by `pred`
+    /// until a node repeats and return the closed part of the walk. The
+    /// predecessors left behind by a relaxation that found a negative cycle
+    /// always lead back into one. Returns `None` if the walk ends first,
+    /// which can only happen when the weights aren't totally ordered.
+    fn negative_cycle(
+        edges: &[Edge<K, N, E>],
+        endpoints: &[(usize, usize)],
+        start: usize,
+        pred: impl Fn(usize) -> Option<usize>,
+    ) -> Option<NegativeCycle<K, N, E>> {
+        let mut seen = HashMap::default();
+        let mut walk = vec![];
+        let mut v = start;
+        while let Some(idx) = pred(v) {
+            if let Some(&pos) = seen.get(&v) {
+                let mut cycle = walk[pos..]
+                    .iter()
+                    .map(|&idx: &usize| edges[idx].clone())
+                    .collect::<Vec<_>>();
+                cycle.reverse();
+                return Some(NegativeCycle(Path { edges: cycle }));
+            }
+            seen.insert(v, walk.len());
+            walk.push(idx);
+            v = endpoints[idx].0;
+        }
+        None
+    }
+
+    /// Compute the shortest paths between every pair of nodes using the
+    /// Floyd-Warshall algorithm. Runs in `O(V^3)` time which makes it a good
+    /// fit for dense graphs. Edge weights may be negative. If the graph
+    /// contains a negative cycle, it is returned as a `NegativeCycle`. The
+    /// cycle is left empty if it can't be traced back, which only happens
+    /// with weights that aren't totally ordered, such as NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (char) => [i32]
+    ///     ('A') => [ ('B', 3), ('C', 8) ]
+    ///     ('B') => [ ('C', 2) ]
+    ///     ('C') => [ ('A', -1) ]
+    /// ];
+    ///
+    /// let apsp = g.floyd_warshall(&|e| *e).unwrap();
+    ///
+    /// assert!(apsp.distance(&'A', &'C') == Some(5));
+    /// assert!(apsp.distance(&'C', &'B') == Some(2));
+    ///
+    /// let path = apsp.path(&'C', &'B').unwrap().to_vec_nodes();
+    ///
+    /// assert!(path == vec![g['C'].clone(), g['A'].clone(), g['B'].clone()]);
+    /// ```
+    pub fn floyd_warshall<W>(
+        &self,
+        weight: &dyn Fn(&E) -> W,
+    ) -> Result<DistanceMatrix<K, N, E, W>, NegativeCycle<K, N, E>>
+    where
+        W: Copy + PartialOrd + Default + Add<Output = W>,
+    {
+        let (index, edges, endpoints) = self.index_edges();
+        let n = index.len();
+        let mut distances = vec![None; n * n];
+        let mut predecessors = vec![None; n * n];
+
+        for i in 0..n {
+            distances[i * n + i] = Some(W::default());
+        }
+        for (idx, (edge, &(u, v))) in edges.iter().zip(endpoints.iter()).enumerate() {
+            let w = weight(edge.value());
+            if distances[u * n + v].is_none_or(|d| w < d) {
+                distances[u * n + v] = Some(w);
+                predecessors[u * n + v] = Some(idx);
+            }
+        }
+        for k in 0..n {
+            for i in 0..n {
+                let Some(ik) = distances[i * n + k] else {
+                    continue;
+                };
+                for j in 0..n {
+                    if let Some(kj) = distances[k * n + j] {
+                        let alt = ik + kj;
+                        if distances[i * n + j].is_none_or(|d| alt < d) {
+                            distances[i * n + j] = Some(alt);
+                            predecessors[i * n + j] = predecessors[k * n + j];
+                        }
+                    }
+                }
+            }
+            // Stop as soon as a negative cycle shows up, the distances would
+            // otherwise keep decreasing without bound.
+            let mut cyclic = (0..n)
+                .filter(|&i| distances[i * n + i].is_some_and(|d| d < W::default()))
+                .peekable();
+            if cyclic.peek().is_some() {
+                let cycle = cyclic.find_map(|i| {
+                    Self::negative_cycle(&edges, &endpoints, i, |v| predecessors[i * n + v])
+                });
+                return Err(cycle.unwrap_or(NegativeCycle(Path { edges: vec![] })));
+            }
+        }
+        Ok(DistanceMatrix {
+            index,
+            distances,
+            predecessors,
+            edges,
+        })
+    }
+
+    /// Compute the shortest paths between every pair of nodes using
+    /// Johnson's algorithm. Edge weights are first made non-negative with a
+    /// potential computed by Bellman-Ford, after which Dijkstra's algorithm
+    /// is run from every node. Runs in `O(V E log V)` time which makes it a
+    /// good fit for sparse graphs. If the graph contains a negative cycle,
+    /// it is returned as a `NegativeCycle`. The cycle is left empty if it
+    /// can't be traced back, which only happens with weights that aren't
+    /// totally ordered, such as NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (char) => [i32]
+    ///     ('A') => [ ('B', 3), ('C', 8) ]
+    ///     ('B') => [ ('C', 2) ]
+    ///     ('C') => [ ('A', -1) ]
+    /// ];
+    ///
+    /// let apsp = g.johnson(&|e| *e).unwrap();
+    ///
+    /// assert!(apsp.distance(&'A', &'C') == Some(5));
+    /// assert!(apsp.distance(&'C', &'B') == Some(2));
+    ///
+    /// let path = apsp.path(&'C', &'B').unwrap().to_vec_nodes();
+    ///
+    /// assert!(path == vec![g['C'].clone(), g['A'].clone(), g['B'].clone()]);
+    /// ```
+    pub fn johnson<W>(
+        &self,
+        weight: &dyn Fn(&E) -> W,
+    ) -> Result<DistanceMatrix<K, N, E, W>, NegativeCycle<K, N, E>>
+    where
+        W: Copy + PartialOrd + Default + Add<Output = W> + Sub<Output = W>,
+    {
+        let (index, edges, endpoints) = self.index_edges();
+        let n = index.len();
+        let weights = edges.iter().map(|e| weight(e.value())).collect::<Vec<_>>();
+
+        // Potentials are the distances from a virtual source connected to
+        // every node with a zero weight edge.
+        let mut potential = vec![W::default(); n];
+        let mut parent = vec![None; n];
+        for round in 0..=n {
+            let mut relaxed = None;
+            for (idx, (&(u, v), &w)) in endpoints.iter().zip(weights.iter()).enumerate() {
+                let alt = potential[u] + w;
+                if alt < potential[v] {
+                    potential[v] = alt;
+                    parent[v] = Some(idx);
+                    relaxed = Some(v);
+                }
+            }
+            let Some(v) = relaxed else {
+                break;
+            };
+            if round == n {
+                let cycle = Self::negative_cycle(&edges, &endpoints, v, |v| parent[v]);
+                return Err(cycle.unwrap_or(NegativeCycle(Path { edges: vec![] })));
+            }
+        }
+
+        let mut adjacent = vec![vec![]; n];
+        for (idx, &(u, _)) in endpoints.iter().enumerate() {
+            adjacent[u].push(idx);
+        }
+
+        let mut distances = vec![None; n * n];
+        let mut predecessors = vec![None; n * n];
+        for s in 0..n {
+            let row = s * n;
+            let mut settled = vec![false; n];
+            let mut queue = BinaryHeap::new();
+
+            distances[row + s] = Some(W::default());
+            queue.push(Visit(W::default(), s));
+
+            while let Some(Visit(dist, u)) = queue.pop() {
+                if settled[u] {
+                    continue;
+                }
+                settled[u] = true;
+                for &idx in &adjacent[u] {
+                    let v = endpoints[idx].1;
+                    if settled[v] {
+                        continue;
+                    }
+                    let alt = dist + weights[idx] + potential[u] - potential[v];
+                    if distances[row + v].is_none_or(|d| alt < d) {
+                        distances[row + v] = Some(alt);
+                        predecessors[row + v] = Some(idx);
+                        queue.push(Visit(alt, v));
+                    }
+                }
+            }
+            for v in 0..n {
+                if let Some(d) = distances[row + v] {
+                    distances[row + v] = Some(d + potential[v] - potential[s]);
+                }
+            }
+        }
+        Ok(DistanceMatrix {
+            index,
+            distances,
+            predecessors,
+            edges,
+        })
+    }
+}
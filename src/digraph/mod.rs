@@ -6,7 +6,7 @@
 //! - `Graph` is a container type for a directed graph.
 //! - `Node` is a node type for a directed graph.
 //! - An edge is denoted by a tuple struct `Edge(u, v, e)` where`u` and `v` are
-//! the source and target node and `e` is the edge parameter.
+//!   the source and target node and `e` is the edge parameter.
 //!
 //! # Example
 //!
@@ -40,11 +40,27 @@
 //! assert!(cycle[2] == g[0]);
 //! ```
 
+mod graph_centrality;
+mod graph_components;
+mod graph_cycles;
+mod graph_flow;
+mod graph_frozen;
+mod graph_isomorphism;
 mod graph_macros;
+mod graph_order;
+mod graph_paths;
 mod graph_serde;
 mod node;
 
+pub use crate::digraph::graph_centrality::PageRank;
+pub use crate::digraph::graph_cycles::Cycles;
+pub use crate::digraph::graph_flow::MaxFlow;
+pub use crate::digraph::graph_frozen::FrozenGraph;
+pub use crate::digraph::graph_isomorphism::{Isomorphism, Mappings};
+pub use crate::digraph::graph_order::Cycle;
+pub use crate::digraph::graph_paths::DistanceMatrix;
 pub use crate::digraph::node::*;
+use crate::traits::{Directed, GraphBase};
 use ahash::{AHashMap as HashMap, AHashSet as HashSet};
 use std::{
     fmt::{Display, Write},
@@ -197,7 +213,9 @@ where
         }
     }
 
-    /// Remove a node from the Graph
+    /// Remove a node from the Graph. The node is isolated, so all of its
+    /// inbound and outbound connections are removed from the nodes that
+    /// remain in the graph.
     ///
     /// # Examples
     ///
@@ -211,12 +229,18 @@ where
     ///
     /// assert!(g.contains(&"A"));
     ///
-    /// g.remove(&"A");
+    /// g["A"].connect(&g["B"], 0);
+    ///
+    /// let a = g.remove(&"A").unwrap();
     ///
     /// assert!(g.contains(&"A") == false);
+    /// assert!(a.is_orphan());
+    /// assert!(g[&"B"].is_root());
     /// ```
     pub fn remove(&mut self, node: &K) -> Option<Node<K, N, E>> {
-        self.nodes.remove(node)
+        let node = self.nodes.remove(node)?;
+        node.isolate();
+        Some(node)
     }
 
     /// Collect nodes into a vector
@@ -441,6 +465,93 @@ where
         ordering
     }
 
+    /// Compute the shortest paths from the node with the given key to every
+    /// reachable node using Dijkstra's algorithm. The weight of each edge is
+    /// computed from the edge value by `weight`. Returns `None` if there is
+    /// no node with the given key in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = digraph![
+    ///     (char) => [u64]
+    ///     ('A') => [ ('B', 4), ('C', 1) ]
+    ///     ('B') => [ ('D', 1) ]
+    ///     ('C') => [ ('B', 2), ('D', 5) ]
+    ///     ('D') => []
+    /// ];
+    ///
+    /// let paths = g.shortest_paths(&'A', &|e| *e).unwrap();
+    ///
+    /// assert!(paths.distance(&'D') == Some(4));
+    ///
+    /// let nodes = paths.path_to(&'D').unwrap().to_vec_nodes();
+    ///
+    /// assert!(nodes == vec![g['A'].clone(), g['C'].clone(), g['B'].clone(), g['D'].clone()]);
+    /// ```
+    pub fn shortest_paths<W>(
+        &self,
+        source: &K,
+        weight: &dyn Fn(&E) -> W,
+    ) -> Option<ShortestPaths<K, N, E, W>>
+    where
+        W: Copy + PartialOrd + Default + std::ops::Add<Output = W>,
+    {
+        self.get(source).map(|node| node.dijkstra(weight).search())
+    }
+
+    /// Compute the shortest paths from the node with the given key to every
+    /// reachable node using the Bellman-Ford algorithm, which allows for
+    /// negative edge weights. If a negative cycle is reachable from the
+    /// source, `BellmanFordError::NegativeCycle` is returned carrying the
+    /// cycle. If there is no node with the given key in the graph,
+    /// `BellmanFordError::SourceNotFound` is returned. Both convert into
+    /// `crate::error::Error` for use with `?`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    /// use gdsl::digraph::{BellmanFordError, NegativeCycle};
+    ///
+    /// let g = digraph![
+    ///     (char) => [i64]
+    ///     ('A') => [ ('B', 4), ('C', 5) ]
+    ///     ('B') => [ ('D', 3) ]
+    ///     ('C') => [ ('B', -3) ]
+    ///     ('D') => []
+    /// ];
+    ///
+    /// let paths = g.bellman_ford(&'A', &|e| *e).unwrap();
+    ///
+    /// assert!(paths.distance(&'D') == Some(5));
+    ///
+    /// g['D'].connect(&g['C'], -1);
+    ///
+    /// match g.bellman_ford(&'A', &|e| *e) {
+    ///     Err(BellmanFordError::NegativeCycle(NegativeCycle(cycle))) => assert!(cycle.len() == 4),
+    ///     _ => panic!("expected a negative cycle"),
+    /// }
+    ///
+    /// assert!(matches!(
+    ///     g.bellman_ford(&'E', &|e| *e),
+    ///     Err(BellmanFordError::SourceNotFound)
+    /// ));
+    /// ```
+    pub fn bellman_ford<W>(
+        &self,
+        source: &K,
+        weight: &dyn Fn(&E) -> W,
+    ) -> Result<ShortestPaths<K, N, E, W>, BellmanFordError<K, N, E>>
+    where
+        W: Copy + PartialOrd + Default + std::ops::Add<Output = W>,
+    {
+        let node = self.get(source).ok_or(BellmanFordError::SourceNotFound)?;
+        Ok(node.bellman_ford(weight).search()?)
+    }
+
     pub fn to_dot(&self) -> String {
         let mut s = String::new();
         s.push_str("digraph {\n");
@@ -455,54 +566,47 @@ where
         s
     }
 
-    fn fmt_attr(attrs: Vec<(String, String)>) -> String {
-        let mut s = String::new();
-        for (k, v) in attrs {
-            write!(&mut s, "[{}=\"{}\"]", k, v).unwrap();
-        }
-        s
-    }
-
     pub fn to_dot_with_attr(
         &self,
         gattr: &dyn Fn(&Self) -> Option<Vec<(String, String)>>,
         nattr: &dyn Fn(&Node<K, N, E>) -> Option<Vec<(String, String)>>,
         eattr: &dyn Fn(&Node<K, N, E>, &Node<K, N, E>, &E) -> Option<Vec<(String, String)>>,
     ) -> String {
-        let mut s = String::new();
-        s.push_str("digraph {\n");
-        if let Some(gattrs) = gattr(self) {
-            for (k, v) in gattrs {
-                s.push_str(&format!("\t{}=\"{}\"\n", k, v));
-            }
-        }
-        for (u_key, node) in self.iter() {
-            s.push_str(&format!("\t{}", u_key.clone()));
-            if let Some(nattr) = nattr(node) {
-                s.push_str(&format!(" {}", Self::fmt_attr(nattr)));
-            }
-            s.push('\n');
-        }
-        for (_, node) in self.iter() {
-            for Edge(u, v, e) in node {
-                s.push_str(&format!("\t{} -> {}", u.key(), v.key()));
-                if let Some(eattrs) = eattr(&u, &v, &e) {
-                    s.push_str(&format!(" {}", Self::fmt_attr(eattrs)));
-                }
-                s.push('\n');
-            }
-        }
-        s.push('}');
-        s
+        GraphBase::to_dot_with_attr(self, gattr, nattr, eattr)
     }
 
     pub fn sizeof(&self) -> usize {
-        let mut size = 0;
-        for (k, node) in self.iter() {
-            size += node.sizeof() + std::mem::size_of_val(k);
-        }
-        size
+        GraphBase::sizeof(self)
+    }
+}
+
+impl<K, N, E> GraphBase for Graph<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    type Node = Node<K, N, E>;
+
+    fn node(&self, key: &K) -> Option<Node<K, N, E>> {
+        self.get(key)
+    }
+
+    fn node_count(&self) -> usize {
+        self.len()
     }
+
+    fn nodes(&self) -> impl Iterator<Item = Node<K, N, E>> + '_ {
+        self.nodes.values().cloned()
+    }
+}
+
+impl<K, N, E> Directed for Graph<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
 }
 
 impl<K, N, E> std::ops::Index<K> for Graph<K, N, E>
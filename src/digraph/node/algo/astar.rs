@@ -0,0 +1,124 @@
+use super::{method::*, path::*, *};
+use crate::visit::Visit;
+use ahash::{AHashMap as HashMap, AHashSet as HashSet};
+use std::{collections::BinaryHeap, fmt::Display, hash::Hash, ops::Add};
+
+pub struct AStar<'a, K, N, E, W>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    root: Node<K, N, E>,
+    target: Option<K>,
+    cost: &'a dyn Fn(&Edge<K, N, E>) -> W,
+    heuristic: &'a dyn Fn(&Node<K, N, E>) -> W,
+    method: Method<'a, K, N, E>,
+    transpose: Transposition,
+}
+
+impl<'a, K, N, E, W> AStar<'a, K, N, E, W>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+    W: Copy + PartialOrd + Default + Add<Output = W>,
+{
+    pub fn new(
+        root: &Node<K, N, E>,
+        cost: &'a dyn Fn(&Edge<K, N, E>) -> W,
+        heuristic: &'a dyn Fn(&Node<K, N, E>) -> W,
+    ) -> Self {
+        AStar {
+            root: root.clone(),
+            target: None,
+            cost,
+            heuristic,
+            method: Method::Empty,
+            transpose: Transposition::Outbound,
+        }
+    }
+
+    pub fn target(mut self, target: &K) -> Self {
+        self.target = Some(target.clone());
+        self
+    }
+
+    pub fn transpose(mut self) -> Self {
+        self.transpose = Transposition::Inbound;
+        self
+    }
+
+    pub fn for_each(mut self, f: ForEach<'a, K, N, E>) -> Self {
+        self.method = Method::ForEach(f);
+        self
+    }
+
+    pub fn filter(mut self, f: Filter<'a, K, N, E>) -> Self {
+        self.method = Method::Filter(f);
+        self
+    }
+
+    pub fn search(&mut self) -> Option<Node<K, N, E>> {
+        self.search_path().map(|(path, _)| match path.last_node() {
+            Some(node) => node.clone(),
+            None => self.root.clone(),
+        })
+    }
+
+    /// Searches for the cheapest path from the root to the target. Returns
+    /// the path together with its total cost or `None` if no target was set
+    /// or the target can't be reached. The heuristic must never overestimate
+    /// the remaining cost to the target for the path to be optimal.
+    pub fn search_path(&mut self) -> Option<(Path<K, N, E>, W)> {
+        let target = self.target.clone()?;
+        let mut costs = HashMap::default();
+        let mut predecessors: HashMap<K, Edge<K, N, E>> = HashMap::default();
+        let mut closed = HashSet::default();
+        let mut queue = BinaryHeap::new();
+
+        costs.insert(self.root.key().clone(), W::default());
+        queue.push(Visit((self.heuristic)(&self.root), self.root.clone()));
+
+        while let Some(Visit(_, node)) = queue.pop() {
+            if !closed.insert(node.key().clone()) {
+                continue;
+            }
+            if node.key() == &target {
+                let mut edges = vec![];
+                let mut current = &target;
+                while let Some(edge) = predecessors.get(current) {
+                    edges.push(edge.clone());
+                    current = edge.0.key();
+                }
+                edges.reverse();
+                return Some((Path { edges }, costs[&target]));
+            }
+            let cost = costs[node.key()];
+            let edges: Vec<Edge<K, N, E>> = match self.transpose {
+                Transposition::Outbound => node.iter_out().collect(),
+                Transposition::Inbound => node.iter_in().map(|edge| edge.reverse()).collect(),
+            };
+            for edge in edges {
+                if !self.method.exec(&edge) {
+                    continue;
+                }
+                let alt = cost + (self.cost)(&edge);
+                let cheaper = match costs.get(edge.1.key()) {
+                    Some(current) => alt < *current,
+                    None => true,
+                };
+                if cheaper {
+                    let v = edge.1.clone();
+                    // An inconsistent heuristic may settle a node too early,
+                    // reopen it when a cheaper way to it is found.
+                    closed.remove(v.key());
+                    costs.insert(v.key().clone(), alt);
+                    predecessors.insert(v.key().clone(), edge);
+                    queue.push(Visit(alt + (self.heuristic)(&v), v));
+                }
+            }
+        }
+        None
+    }
+}
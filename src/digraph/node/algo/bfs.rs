@@ -1,6 +1,10 @@
 use super::{method::*, path::*, *};
 use ahash::AHashSet as HashSet;
-use std::{collections::VecDeque, fmt::Display, hash::Hash};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+    hash::Hash,
+};
 
 pub struct Bfs<'a, K, N, E>
 where
@@ -8,10 +12,17 @@ where
     N: Clone,
     E: Clone,
 {
-    root: Node<K, N, E>,
+    roots: Vec<Node<K, N, E>>,
     target: Option<K>,
     method: Method<'a, K, N, E>,
     transpose: Transposition,
+    max_depth: Option<usize>,
+    started: bool,
+    queue: VecDeque<(Node<K, N, E>, usize)>,
+    edges: VecDeque<Edge<K, N, E>>,
+    depth: usize,
+    visited: HashSet<K>,
+    origins: HashMap<K, K>,
 }
 
 impl<'a, K, N, E> Bfs<'a, K, N, E>
@@ -21,11 +32,28 @@ where
     E: Clone,
 {
     pub fn new(root: &Node<K, N, E>) -> Self {
+        Self::from_roots([root.clone()])
+    }
+
+    /// Create a search that starts from all of `roots` at once. Every root
+    /// is at depth zero, so each node is reached from the root closest to
+    /// it. Use `origin` to find out which root that was.
+    pub fn from_roots<I>(roots: I) -> Self
+    where
+        I: IntoIterator<Item = Node<K, N, E>>,
+    {
         Bfs {
-            root: root.clone(),
+            roots: roots.into_iter().collect(),
             target: None,
             method: Method::Empty,
             transpose: Transposition::Outbound,
+            max_depth: None,
+            started: false,
+            queue: VecDeque::new(),
+            edges: VecDeque::new(),
+            depth: 0,
+            visited: HashSet::default(),
+            origins: HashMap::new(),
         }
     }
 
@@ -39,6 +67,12 @@ where
         self
     }
 
+    /// Only visit nodes at most `max_depth` edges away from the root.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
     pub fn for_each(mut self, f: ForEach<'a, K, N, E>) -> Self {
         self.method = Method::ForEach(f);
         self
@@ -49,166 +83,128 @@ where
         self
     }
 
-    pub fn search(&'a mut self) -> Option<Node<K, N, E>> {
-        let mut queue = VecDeque::new();
-        let mut visited = HashSet::default();
+    /// Like `for_each` but the closure also receives the depth of the edge,
+    /// the number of edges from the root to its target.
+    pub fn for_each_depth(mut self, f: ForEachDepth<'a, K, N, E>) -> Self {
+        self.method = Method::ForEachDepth(f);
+        self
+    }
 
-        queue.push_back(self.root.clone());
-        visited.insert(self.root.key().clone());
+    /// Like `filter` but the closure also receives the depth of the edge,
+    /// the number of edges from the root to its target.
+    pub fn filter_depth(mut self, f: FilterDepth<'a, K, N, E>) -> Self {
+        self.method = Method::FilterDepth(f);
+        self
+    }
 
+    fn adjacent(&self, node: &Node<K, N, E>) -> VecDeque<Edge<K, N, E>> {
         match self.transpose {
-            Transposition::Outbound => self.loop_outbound_find(&mut visited, &mut queue),
-            Transposition::Inbound => self.loop_inbound_find(&mut visited, &mut queue),
+            Transposition::Outbound => node.iter_out().collect(),
+            Transposition::Inbound => node.iter_in().map(|edge| edge.reverse()).collect(),
         }
     }
 
-    pub fn search_cycle(&'a mut self) -> Option<Path<K, N, E>> {
-        let mut edges = vec![];
-        let mut queue = VecDeque::new();
-        let mut visited = HashSet::default();
+    /// Returns the key of the root from which the node with the given key
+    /// was reached, or `None` if the node has not been visited. A root is
+    /// its own origin.
+    pub fn origin(&self, key: &K) -> Option<&K> {
+        self.origins.get(key)
+    }
 
-        self.target = Some(self.root.key().clone());
-        queue.push_back(self.root.clone());
+    /// Returns the origin of every node visited so far keyed by node key.
+    pub fn origins(&self) -> &HashMap<K, K> {
+        &self.origins
+    }
 
-        match self.transpose {
-            Transposition::Outbound => {
-                match self.loop_outbound(&mut edges, &mut visited, &mut queue) {
-                    true => Some(Path::from_edge_tree(edges)),
-                    false => None,
-                }
+    /// Start the traversal over from the roots. When searching for a cycle
+    /// only the first root is used and it is left unvisited so that an edge
+    /// back to it can be found.
+    fn restart(&mut self, cycle: bool) {
+        self.started = true;
+        self.queue.clear();
+        self.edges.clear();
+        self.visited.clear();
+        self.origins.clear();
+        let count = match cycle {
+            true => 1,
+            false => self.roots.len(),
+        };
+        for root in self.roots.iter().take(count) {
+            let key = root.key().clone();
+            if self.origins.insert(key.clone(), key.clone()).is_none() {
+                self.queue.push_back((root.clone(), 0));
             }
-            Transposition::Inbound => {
-                match self.loop_inbound(&mut edges, &mut visited, &mut queue) {
-                    true => Some(Path::from_edge_tree(edges)),
-                    false => None,
-                }
+            if !cycle {
+                self.visited.insert(key);
             }
         }
     }
 
-    pub fn search_path(&mut self) -> Option<Path<K, N, E>> {
-        let mut edges = vec![];
-        let mut queue = VecDeque::new();
-        let mut visited = HashSet::default();
+    pub fn search(&mut self) -> Option<Node<K, N, E>> {
+        self.restart(false);
+        let target = self.target.clone();
+        self.find(|edge| target.as_ref() == Some(edge.target().key()))
+            .map(|edge| edge.target().clone())
+    }
 
-        queue.push_back(self.root.clone());
-        visited.insert(self.root.key().clone());
+    pub fn search_cycle(&mut self) -> Option<Path<K, N, E>> {
+        self.target = Some(self.roots.first()?.key().clone());
+        self.restart(true);
+        self.collect_path()
+    }
 
-        match self.transpose {
-            Transposition::Outbound => {
-                match self.loop_outbound(&mut edges, &mut visited, &mut queue) {
-                    true => Some(Path::from_edge_tree(edges)),
-                    false => None,
-                }
-            }
-            Transposition::Inbound => {
-                match self.loop_inbound(&mut edges, &mut visited, &mut queue) {
-                    true => Some(Path::from_edge_tree(edges)),
-                    false => None,
-                }
-            }
-        }
+    pub fn search_path(&mut self) -> Option<Path<K, N, E>> {
+        self.restart(false);
+        self.collect_path()
     }
 
-    fn loop_outbound(
-        &mut self,
-        result: &mut Vec<Edge<K, N, E>>,
-        visited: &mut HashSet<K>,
-        queue: &mut VecDeque<Node<K, N, E>>,
-    ) -> bool {
-        while let Some(node) = queue.pop_front() {
-            for edge in node.iter_out() {
-                if self.method.exec(&edge) {
-                    let v = edge.1.clone();
-                    if !visited.contains(v.key()) {
-                        visited.insert(v.key().clone());
-                        result.push(edge);
-                        if let Some(ref t) = self.target {
-                            if v.key() == t {
-                                return true;
-                            }
-                        }
-                        queue.push_back(v);
-                    }
-                }
-            }
+    fn collect_path(&mut self) -> Option<Path<K, N, E>> {
+        let target = self.target.clone()?;
+        let edges = self.by_ref().collect::<Vec<_>>();
+        match edges.last() {
+            Some(edge) if edge.target().key() == &target => Some(Path::from_edge_tree(edges)),
+            _ => None,
         }
-        false
-    }
-
-    fn loop_inbound(
-        &mut self,
-        result: &mut Vec<Edge<K, N, E>>,
-        visited: &mut HashSet<K>,
-        queue: &mut VecDeque<Node<K, N, E>>,
-    ) -> bool {
-        while let Some(node) = queue.pop_front() {
-            for edge in node.iter_in() {
-                let edge = edge.reverse();
-                if self.method.exec(&edge) {
-                    let v = edge.1.clone();
-                    if !visited.contains(v.key()) {
-                        visited.insert(v.key().clone());
-                        result.push(edge);
-                        if let Some(ref t) = self.target {
-                            if v.key() == t {
-                                return true;
-                            }
-                        }
-                        queue.push_back(v);
-                    }
-                }
-            }
+    }
+}
+
+/// Iterating a `Bfs` yields the edges of the breadth-first search tree in
+/// the order the search discovers them. The iteration stops after the edge
+/// that reaches the target, if one is set.
+impl<'a, K, N, E> Iterator for Bfs<'a, K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    type Item = Edge<K, N, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.restart(false);
         }
-        false
-    }
-
-    fn loop_outbound_find(
-        &mut self,
-        visited: &mut HashSet<K>,
-        queue: &mut VecDeque<Node<K, N, E>>,
-    ) -> Option<Node<K, N, E>> {
-        while let Some(node) = queue.pop_front() {
-            for edge in node.iter_out() {
-                if self.method.exec(&edge) {
-                    let Edge(_, v, _) = edge;
-                    if !visited.contains(v.key()) {
-                        visited.insert(v.key().clone());
-                        if let Some(ref t) = self.target {
-                            if v.key() == t {
-                                return Some(v);
-                            }
-                        }
-                        queue.push_back(v);
+        loop {
+            while let Some(edge) = self.edges.pop_front() {
+                if self.method.exec_at(&edge, self.depth + 1)
+                    && self.visited.insert(edge.target().key().clone())
+                {
+                    let origin = self.origins[edge.source().key()].clone();
+                    self.origins.insert(edge.target().key().clone(), origin);
+                    if self.target.as_ref() == Some(edge.target().key()) {
+                        self.queue.clear();
+                        self.edges.clear();
+                    } else {
+                        self.queue
+                            .push_back((edge.target().clone(), self.depth + 1));
                     }
+                    return Some(edge);
                 }
             }
-        }
-        None
-    }
-
-    fn loop_inbound_find(
-        &mut self,
-        visited: &mut HashSet<K>,
-        queue: &mut VecDeque<Node<K, N, E>>,
-    ) -> Option<Node<K, N, E>> {
-        while let Some(node) = queue.pop_front() {
-            for edge in node.iter_in() {
-                let edge = edge.reverse();
-                if self.method.exec(&edge) {
-                    let Edge(_, v, _) = edge;
-                    if !visited.contains(v.key()) {
-                        visited.insert(v.key().clone());
-                        if let Some(ref t) = self.target {
-                            if v.key() == t {
-                                return Some(v);
-                            }
-                        }
-                        queue.push_back(v);
-                    }
-                }
+            let (node, depth) = self.queue.pop_front()?;
+            if self.max_depth.is_none_or(|max| depth < max) {
+                self.edges = self.adjacent(&node);
+                self.depth = depth;
             }
         }
-        None
     }
 }
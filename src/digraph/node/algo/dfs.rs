@@ -1,6 +1,6 @@
-use super::{method::*, path::*, *};
-use ahash::AHashSet as HashSet;
-use std::{fmt::Display, hash::Hash};
+use super::{method::*, path::*, visitor::*, *};
+use ahash::{AHashMap as HashMap, AHashSet as HashSet};
+use std::{collections::VecDeque, fmt::Display, hash::Hash};
 
 pub struct Dfs<'a, K, N, E>
 where
@@ -8,10 +8,21 @@ where
     N: Clone,
     E: Clone,
 {
-    root: Node<K, N, E>,
+    roots: Vec<Node<K, N, E>>,
     target: Option<K>,
     method: Method<'a, K, N, E>,
     transpose: Transposition,
+    max_depth: Option<usize>,
+    deepening: bool,
+    started: bool,
+    limit: usize,
+    found: bool,
+    seeds: usize,
+    position: usize,
+    stack: Vec<VecDeque<Edge<K, N, E>>>,
+    depths: HashMap<K, usize>,
+    visited: HashSet<K>,
+    origins: std::collections::HashMap<K, K>,
 }
 
 impl<'a, K, N, E> Dfs<'a, K, N, E>
@@ -21,11 +32,32 @@ where
     E: Clone,
 {
     pub fn new(root: &Node<K, N, E>) -> Self {
+        Self::from_roots([root.clone()])
+    }
+
+    /// Create a search that starts from each of `roots` in turn. All the
+    /// roots are visited up front at depth zero, so no root is entered from
+    /// another. Use `origin` to find out which root reached a node.
+    pub fn from_roots<I>(roots: I) -> Self
+    where
+        I: IntoIterator<Item = Node<K, N, E>>,
+    {
         Dfs {
-            root: root.clone(),
+            roots: roots.into_iter().collect(),
             target: None,
             method: Method::Empty,
             transpose: Transposition::Outbound,
+            max_depth: None,
+            deepening: false,
+            started: false,
+            limit: 0,
+            found: false,
+            seeds: 0,
+            position: 0,
+            stack: vec![],
+            depths: HashMap::default(),
+            visited: HashSet::default(),
+            origins: std::collections::HashMap::new(),
         }
     }
 
@@ -39,6 +71,28 @@ where
         self
     }
 
+    /// Only visit nodes at most `max_depth` edges away from the root. A node
+    /// reached again through a shorter path is searched again so that every
+    /// node within the depth is visited.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Search with iterative deepening. The search is repeated with the depth
+    /// limit raised by one each time until no new nodes are found or
+    /// `max_depth` is reached. Nodes are visited in order of their distance
+    /// from the root like in a breadth-first search, but only the current
+    /// path is kept on the stack.
+    ///
+    /// Every pass starts over from the roots, so the `filter` and `for_each`
+    /// closures are run again on the edges covered by the earlier passes.
+    /// Act on the yielded edges instead to handle each node only once.
+    pub fn iterative_deepening(mut self) -> Self {
+        self.deepening = true;
+        self
+    }
+
     pub fn for_each(mut self, f: ForEach<'a, K, N, E>) -> Self {
         self.method = Method::ForEach(f);
         self
@@ -49,180 +103,259 @@ where
         self
     }
 
-    pub fn search(&'a mut self) -> Option<Node<K, N, E>> {
-        let mut queue = vec![];
-        let mut visited = HashSet::default();
+    /// Like `for_each` but the closure also receives the depth of the edge,
+    /// the number of edges on the current path from the root to its target.
+    pub fn for_each_depth(mut self, f: ForEachDepth<'a, K, N, E>) -> Self {
+        self.method = Method::ForEachDepth(f);
+        self
+    }
 
-        queue.push(self.root.clone());
-        visited.insert(self.root.key().clone());
+    /// Like `filter` but the closure also receives the depth of the edge,
+    /// the number of edges on the current path from the root to its target.
+    pub fn filter_depth(mut self, f: FilterDepth<'a, K, N, E>) -> Self {
+        self.method = Method::FilterDepth(f);
+        self
+    }
 
+    fn adjacent(&self, node: &Node<K, N, E>) -> VecDeque<Edge<K, N, E>> {
         match self.transpose {
-            Transposition::Outbound => self.recurse_outbound_find(&mut visited, &mut queue),
-            Transposition::Inbound => self.recurse_inbound_find(&mut visited, &mut queue),
+            Transposition::Outbound => node.iter_out().collect(),
+            Transposition::Inbound => node.iter_in().map(|edge| edge.reverse()).collect(),
         }
     }
 
-    pub fn search_cycle(&'a mut self) -> Option<Path<K, N, E>> {
-        let mut edges = vec![];
-        let mut queue = vec![];
-        let mut visited = HashSet::default();
+    /// Returns the key of the root from which the node with the given key
+    /// was reached, or `None` if the node has not been visited. A root is
+    /// its own origin.
+    pub fn origin(&self, key: &K) -> Option<&K> {
+        self.origins.get(key)
+    }
 
-        self.target = Some(self.root.key().clone());
-        queue.push(self.root.clone());
+    /// Returns the origin of every node visited so far keyed by node key.
+    pub fn origins(&self) -> &std::collections::HashMap<K, K> {
+        &self.origins
+    }
 
-        match self.transpose {
-            Transposition::Outbound => {
-                match self.recurse_outbound(&mut edges, &mut visited, &mut queue) {
-                    true => Some(Path::from_edge_tree(edges)),
-                    false => None,
-                }
-            }
-            Transposition::Inbound => {
-                match self.recurse_inbound(&mut edges, &mut visited, &mut queue) {
-                    true => Some(Path::from_edge_tree(edges)),
-                    false => None,
-                }
+    /// Start the traversal over from the roots. When searching for a cycle
+    /// only the first root is used and it is left unvisited so that an edge
+    /// back to it can be found.
+    fn restart(&mut self, cycle: bool) {
+        self.started = true;
+        self.visited.clear();
+        self.origins.clear();
+        self.limit = match self.deepening {
+            true => 0,
+            false => self.max_depth.unwrap_or(usize::MAX),
+        };
+        self.seeds = match cycle {
+            true => self.roots.len().min(1),
+            false => self.roots.len(),
+        };
+        for root in &self.roots[..self.seeds] {
+            let key = root.key().clone();
+            self.origins.insert(key.clone(), key.clone());
+            if !cycle {
+                self.visited.insert(key);
             }
         }
+        self.deepen();
     }
 
-    pub fn search_path(&mut self) -> Option<Path<K, N, E>> {
-        let mut edges = vec![];
-        let mut queue = vec![];
-        let mut visited = HashSet::default();
+    /// Begin a new pass of the search from the first root.
+    fn deepen(&mut self) {
+        if self.deepening {
+            self.limit += 1;
+        }
+        self.found = false;
+        self.depths.clear();
+        for root in &self.roots[..self.seeds] {
+            self.depths.insert(root.key().clone(), 0);
+        }
+        self.stack.clear();
+        self.position = 0;
+    }
 
-        queue.push(self.root.clone());
-        visited.insert(self.root.key().clone());
+    pub fn search(&mut self) -> Option<Node<K, N, E>> {
+        self.restart(false);
+        let target = self.target.clone();
+        self.find(|edge| target.as_ref() == Some(edge.target().key()))
+            .map(|edge| edge.target().clone())
+    }
 
-        match self.transpose {
-            Transposition::Outbound => {
-                match self.recurse_outbound(&mut edges, &mut visited, &mut queue) {
-                    true => Some(Path::from_edge_tree(edges)),
-                    false => None,
-                }
-            }
-            Transposition::Inbound => {
-                match self.recurse_inbound(&mut edges, &mut visited, &mut queue) {
-                    true => Some(Path::from_edge_tree(edges)),
-                    false => None,
-                }
-            }
+    pub fn search_cycle(&mut self) -> Option<Path<K, N, E>> {
+        self.target = Some(self.roots.first()?.key().clone());
+        self.restart(true);
+        self.collect_path()
+    }
+
+    pub fn search_path(&mut self) -> Option<Path<K, N, E>> {
+        self.restart(false);
+        self.collect_path()
+    }
+
+    fn collect_path(&mut self) -> Option<Path<K, N, E>> {
+        let target = self.target.clone()?;
+        let edges = self.by_ref().collect::<Vec<_>>();
+        match edges.last() {
+            Some(edge) if edge.target().key() == &target => Some(Path::from_edge_tree(edges)),
+            _ => None,
         }
     }
 
-    fn recurse_outbound(
-        &mut self,
-        result: &mut Vec<Edge<K, N, E>>,
-        visited: &mut HashSet<K>,
-        queue: &mut Vec<Node<K, N, E>>,
-    ) -> bool {
-        if let Some(node) = queue.pop() {
-            for edge in node.iter_out() {
-                if self.method.exec(&edge) {
-                    let v = edge.target().clone();
-                    if !visited.contains(v.key()) {
-                        visited.insert(v.key().clone());
-                        result.push(edge);
-                        if let Some(ref t) = self.target {
-                            if v.key() == t {
-                                return true;
-                            }
-                        }
-                        queue.push(v.clone());
-                        if self.recurse_outbound(result, visited, queue) {
-                            return true;
-                        }
+    /// Run the search and report its events to `visitor`. The search starts
+    /// from each root in turn unless the root was already discovered from
+    /// an earlier one, so building the search from every node of a graph
+    /// visits the whole graph. The `filter` and `for_each` closures are run
+    /// on each edge before it is classified and `target` and `max_depth`
+    /// are ignored. Returns `Control::Stop` if the visitor stopped the
+    /// search and `Control::Continue` otherwise.
+    pub fn visit<V>(&mut self, visitor: &mut V) -> Control
+    where
+        V: Visitor<K, N, E> + ?Sized,
+    {
+        let mut discovered = HashSet::default();
+        let mut finished = HashSet::default();
+        let mut stack = vec![];
+
+        for root in self.roots.clone() {
+            if discovered.contains(root.key()) {
+                continue;
+            }
+            let control = self.enter(&root, visitor, &mut discovered, &mut finished, &mut stack);
+            if control == Control::Stop {
+                return Control::Stop;
+            }
+            while let Some((_, edges)) = stack.last_mut() {
+                let Some(edge) = edges.pop_front() else {
+                    let (node, _) = stack.pop().unwrap();
+                    finished.insert(node.key().clone());
+                    if visitor.finish_node(&node) == Control::Stop {
+                        return Control::Stop;
                     }
+                    continue;
+                };
+                if !self.method.exec(&edge) {
+                    continue;
                 }
-            }
-        }
-        false
-    }
-
-    fn recurse_inbound(
-        &mut self,
-        result: &mut Vec<Edge<K, N, E>>,
-        visited: &mut HashSet<K>,
-        queue: &mut Vec<Node<K, N, E>>,
-    ) -> bool {
-        if let Some(node) = queue.pop() {
-            for edge in node.iter_in() {
-                let edge = edge.reverse();
-                if self.method.exec(&edge) {
-                    let v = edge.target().clone();
-                    if !visited.contains(v.key()) {
-                        visited.insert(v.key().clone());
-                        result.push(edge);
-                        if let Some(ref t) = self.target {
-                            if v.key() == t {
-                                return true;
-                            }
-                        }
-                        queue.push(v.clone());
-                        if self.recurse_inbound(result, visited, queue) {
-                            return true;
+                let v = edge.target();
+                let control = if !discovered.contains(v.key()) {
+                    match visitor.tree_edge(&edge) {
+                        Control::Continue => {
+                            self.enter(v, visitor, &mut discovered, &mut finished, &mut stack)
                         }
+                        control => control,
                     }
+                } else if !finished.contains(v.key()) {
+                    visitor.back_edge(&edge)
+                } else {
+                    visitor.forward_or_cross_edge(&edge)
+                };
+                if control == Control::Stop {
+                    return Control::Stop;
                 }
             }
         }
-        false
-    }
-
-    fn recurse_outbound_find(
-        &mut self,
-        visited: &mut HashSet<K>,
-        queue: &mut Vec<Node<K, N, E>>,
-    ) -> Option<Node<K, N, E>> {
-        if let Some(node) = queue.pop() {
-            for edge in node.iter_out() {
-                if self.method.exec(&edge) {
-                    let v = edge.target();
-                    if !visited.contains(v.key()) {
-                        visited.insert(v.key().clone());
-                        if let Some(ref t) = self.target {
-                            if v.key() == t {
-                                return Some(v.clone());
-                            }
-                        }
-                        queue.push(v.clone());
-                        match self.recurse_outbound_find(visited, queue) {
-                            Some(t) => return Some(t),
-                            None => continue,
-                        }
-                    }
-                }
+        Control::Continue
+    }
+
+    /// Discover `node` and push it on the stack of `visit`. A node pruned by
+    /// the visitor is finished right away.
+    fn enter<V>(
+        &self,
+        node: &Node<K, N, E>,
+        visitor: &mut V,
+        discovered: &mut HashSet<K>,
+        finished: &mut HashSet<K>,
+        stack: &mut Vec<(Node<K, N, E>, VecDeque<Edge<K, N, E>>)>,
+    ) -> Control
+    where
+        V: Visitor<K, N, E> + ?Sized,
+    {
+        discovered.insert(node.key().clone());
+        match visitor.discover_node(node) {
+            Control::Continue => {
+                stack.push((node.clone(), self.adjacent(node)));
+                Control::Continue
+            }
+            Control::Prune => {
+                finished.insert(node.key().clone());
+                visitor.finish_node(node)
             }
+            Control::Stop => Control::Stop,
         }
-        None
-    }
-
-    fn recurse_inbound_find(
-        &mut self,
-        visited: &mut HashSet<K>,
-        queue: &mut Vec<Node<K, N, E>>,
-    ) -> Option<Node<K, N, E>> {
-        if let Some(node) = queue.pop() {
-            for edge in node.iter_in() {
-                let edge = edge.reverse();
-                if self.method.exec(&edge) {
-                    let v = edge.target();
-                    if !visited.contains(v.key()) {
-                        visited.insert(v.key().clone());
-                        if let Some(ref t) = self.target {
-                            if v.key() == t {
-                                return Some(v.clone());
-                            }
-                        }
-                        queue.push(v.clone());
-                        match self.recurse_inbound_find(visited, queue) {
-                            Some(t) => return Some(t),
-                            None => continue,
-                        }
+    }
+}
+
+/// Iterating a `Dfs` yields the edges of the depth-first search tree in
+/// the order the search discovers them. The iteration stops after the edge
+/// that reaches the target, if one is set.
+impl<'a, K, N, E> Iterator for Dfs<'a, K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    type Item = Edge<K, N, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.restart(false);
+        }
+        let bounded = self.deepening || self.max_depth.is_some();
+        loop {
+            let Some(edges) = self.stack.last_mut() else {
+                let within = self.max_depth.is_none_or(|max| self.limit <= max);
+                if self.position < self.seeds && self.limit > 0 && within {
+                    let edges = self.adjacent(&self.roots[self.position]);
+                    self.position += 1;
+                    self.stack.push(edges);
+                    continue;
+                }
+                let more = self.max_depth.is_none_or(|max| self.limit < max);
+                if self.deepening && self.found && more {
+                    self.deepen();
+                    continue;
+                }
+                return None;
+            };
+            let Some(edge) = edges.pop_front() else {
+                self.stack.pop();
+                continue;
+            };
+            let depth = self.stack.len();
+            let v = edge.target().clone();
+            if !self.method.exec_at(&edge, depth) {
+                continue;
+            }
+            let new = self.visited.insert(v.key().clone());
+            let expand = match bounded {
+                true => {
+                    let shorter = self.depths.get(v.key()).is_none_or(|&d| depth < d);
+                    if shorter {
+                        self.depths.insert(v.key().clone(), depth);
                     }
+                    shorter && depth < self.limit
                 }
+                false => new,
+            };
+            if new || expand {
+                let origin = self.origins[edge.source().key()].clone();
+                self.origins.insert(v.key().clone(), origin);
+            }
+            if new && self.target.as_ref() == Some(v.key()) {
+                self.stack.clear();
+                self.position = self.seeds;
+                self.found = false;
+                return Some(edge);
+            }
+            if expand {
+                let edges = self.adjacent(&v);
+                self.stack.push(edges);
+            }
+            if new {
+                self.found = true;
+                return Some(edge);
             }
         }
-        None
     }
 }
@@ -0,0 +1,179 @@
+use super::{method::*, path::*, *};
+use crate::visit::Visit;
+use ahash::{AHashMap as HashMap, AHashSet as HashSet};
+use std::{collections::BinaryHeap, fmt::Display, hash::Hash, ops::Add};
+
+/// Result of a shortest path search. Holds the distance of every reached
+/// node from the source and the edge through which each node was reached.
+/// Distances are kept in a side table, the node values are never touched.
+pub struct ShortestPaths<K, N, E, W>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    source: Node<K, N, E>,
+    distances: std::collections::HashMap<K, W>,
+    predecessors: HashMap<K, Edge<K, N, E>>,
+}
+
+impl<K, N, E, W> ShortestPaths<K, N, E, W>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+    W: Copy,
+{
+    pub(super) fn new(
+        source: Node<K, N, E>,
+        distances: HashMap<K, W>,
+        predecessors: HashMap<K, Edge<K, N, E>>,
+    ) -> Self {
+        ShortestPaths {
+            source,
+            distances: distances.into_iter().collect(),
+            predecessors,
+        }
+    }
+
+    /// Returns the source node of the search.
+    pub fn source(&self) -> &Node<K, N, E> {
+        &self.source
+    }
+
+    /// Returns the distance from the source to the node with the given key
+    /// or `None` if the node was not reached.
+    pub fn distance(&self, target: &K) -> Option<W> {
+        self.distances.get(target).copied()
+    }
+
+    /// Returns the distances of all reached nodes keyed by node key.
+    pub fn distances(&self) -> &std::collections::HashMap<K, W> {
+        &self.distances
+    }
+
+    /// Returns the edge through which the node with the given key was
+    /// reached on its shortest path.
+    pub fn predecessor(&self, target: &K) -> Option<&Edge<K, N, E>> {
+        self.predecessors.get(target)
+    }
+
+    /// Returns true if the node with the given key was reached.
+    pub fn contains(&self, target: &K) -> bool {
+        self.distances.contains_key(target)
+    }
+
+    /// Backtracks the predecessor tree from the given target to the source.
+    /// Returns `None` if the target was not reached or is the source itself.
+    pub fn path_to(&self, target: &K) -> Option<Path<K, N, E>> {
+        let mut edges = vec![];
+        let mut current = target;
+        while let Some(edge) = self.predecessors.get(current) {
+            edges.push(edge.clone());
+            current = edge.0.key();
+        }
+        if edges.is_empty() {
+            return None;
+        }
+        edges.reverse();
+        Some(Path { edges })
+    }
+}
+
+pub struct Dijkstra<'a, K, N, E, W>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    root: Node<K, N, E>,
+    target: Option<K>,
+    weight: &'a dyn Fn(&E) -> W,
+    method: Method<'a, K, N, E>,
+    transpose: Transposition,
+}
+
+impl<'a, K, N, E, W> Dijkstra<'a, K, N, E, W>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+    W: Copy + PartialOrd + Default + Add<Output = W>,
+{
+    pub fn new(root: &Node<K, N, E>, weight: &'a dyn Fn(&E) -> W) -> Self {
+        Dijkstra {
+            root: root.clone(),
+            target: None,
+            weight,
+            method: Method::Empty,
+            transpose: Transposition::Outbound,
+        }
+    }
+
+    pub fn target(mut self, target: &K) -> Self {
+        self.target = Some(target.clone());
+        self
+    }
+
+    pub fn transpose(mut self) -> Self {
+        self.transpose = Transposition::Inbound;
+        self
+    }
+
+    pub fn for_each(mut self, f: ForEach<'a, K, N, E>) -> Self {
+        self.method = Method::ForEach(f);
+        self
+    }
+
+    pub fn filter(mut self, f: Filter<'a, K, N, E>) -> Self {
+        self.method = Method::Filter(f);
+        self
+    }
+
+    pub fn search(&mut self) -> ShortestPaths<K, N, E, W> {
+        let mut distances = HashMap::default();
+        let mut predecessors = HashMap::default();
+        let mut settled = HashSet::default();
+        let mut queue = BinaryHeap::new();
+
+        distances.insert(self.root.key().clone(), W::default());
+        queue.push(Visit(W::default(), self.root.clone()));
+
+        while let Some(Visit(dist, node)) = queue.pop() {
+            if !settled.insert(node.key().clone()) {
+                continue;
+            }
+            if let Some(ref t) = self.target {
+                if node.key() == t {
+                    break;
+                }
+            }
+            let edges: Vec<Edge<K, N, E>> = match self.transpose {
+                Transposition::Outbound => node.iter_out().collect(),
+                Transposition::Inbound => node.iter_in().map(|edge| edge.reverse()).collect(),
+            };
+            for edge in edges {
+                if !self.method.exec(&edge) || settled.contains(edge.1.key()) {
+                    continue;
+                }
+                let alt = dist + (self.weight)(&edge.2);
+                let shorter = match distances.get(edge.1.key()) {
+                    Some(current) => alt < *current,
+                    None => true,
+                };
+                if shorter {
+                    let v = edge.1.clone();
+                    distances.insert(v.key().clone(), alt);
+                    predecessors.insert(v.key().clone(), edge);
+                    queue.push(Visit(alt, v));
+                }
+            }
+        }
+        ShortestPaths::new(self.root.clone(), distances, predecessors)
+    }
+
+    pub fn search_path(&mut self) -> Option<Path<K, N, E>> {
+        let target = self.target.clone()?;
+        self.search().path_to(&target)
+    }
+}
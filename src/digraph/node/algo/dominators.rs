@@ -0,0 +1,323 @@
+use super::*;
+use ahash::AHashMap as HashMap;
+use std::{fmt::Display, hash::Hash};
+
+pub struct Dominators<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    root: Node<K, N, E>,
+    transpose: Transposition,
+}
+
+impl<K, N, E> Dominators<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    pub fn new(root: &Node<K, N, E>) -> Self {
+        Dominators {
+            root: root.clone(),
+            transpose: Transposition::Outbound,
+        }
+    }
+
+    /// Follow inbound edges instead of outbound ones. With the exit node of
+    /// a control-flow graph as the root this computes post-dominators.
+    pub fn transpose(mut self) -> Self {
+        self.transpose = Transposition::Inbound;
+        self
+    }
+
+    fn successors(&self, node: &Node<K, N, E>) -> Vec<Node<K, N, E>> {
+        match self.transpose {
+            Transposition::Outbound => node.iter_out().map(|Edge(_, v, _)| v).collect(),
+            Transposition::Inbound => node.iter_in().map(|Edge(u, _, _)| u).collect(),
+        }
+    }
+
+    /// Compute the dominator tree of the nodes reachable from the root with
+    /// the iterative algorithm of Cooper, Harvey and Kennedy. Nodes are
+    /// numbered in reverse postorder so the root is `0` and the immediate
+    /// dominators are refined until they no longer change.
+    pub fn search(&self) -> DominatorTree<K, N, E> {
+        // Number the reachable nodes in the order they are discovered.
+        let mut nodes = vec![self.root.clone()];
+        let mut index = HashMap::default();
+        let mut successors: Vec<Vec<usize>> = vec![];
+        index.insert(self.root.key().clone(), 0);
+        let mut i = 0;
+        while i < nodes.len() {
+            let adjacent = self
+                .successors(&nodes[i])
+                .into_iter()
+                .map(|v| match index.get(v.key()) {
+                    Some(&j) => j,
+                    None => {
+                        index.insert(v.key().clone(), nodes.len());
+                        nodes.push(v);
+                        nodes.len() - 1
+                    }
+                })
+                .collect();
+            successors.push(adjacent);
+            i += 1;
+        }
+
+        // Postorder by an iterative depth-first search.
+        let mut postorder = Vec::with_capacity(nodes.len());
+        let mut visited = vec![false; nodes.len()];
+        let mut stack = vec![(0, 0)];
+        visited[0] = true;
+        while let Some((u, position)) = stack.last_mut() {
+            match successors[*u].get(*position) {
+                Some(&v) => {
+                    *position += 1;
+                    if !visited[v] {
+                        visited[v] = true;
+                        stack.push((v, 0));
+                    }
+                }
+                None => {
+                    postorder.push(*u);
+                    stack.pop();
+                }
+            }
+        }
+
+        // Renumber in reverse postorder.
+        let mut order = vec![0; nodes.len()];
+        for (rank, &u) in postorder.iter().rev().enumerate() {
+            order[u] = rank;
+        }
+        let mut predecessors = vec![vec![]; nodes.len()];
+        for (u, adjacent) in successors.iter().enumerate() {
+            for &v in adjacent {
+                predecessors[order[v]].push(order[u]);
+            }
+        }
+        let mut nodes = nodes.into_iter().map(Some).collect::<Vec<_>>();
+        let nodes = postorder
+            .iter()
+            .rev()
+            .map(|&u| nodes[u].take().unwrap())
+            .collect::<Vec<_>>();
+
+        let mut idom = vec![None; nodes.len()];
+        idom[0] = Some(0);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for b in 1..nodes.len() {
+                let mut new = None;
+                for &p in &predecessors[b] {
+                    if idom[p].is_none() {
+                        continue;
+                    }
+                    new = match new {
+                        None => Some(p),
+                        Some(q) => Some(intersect(&idom, p, q)),
+                    };
+                }
+                if new.is_some() && idom[b] != new {
+                    idom[b] = new;
+                    changed = true;
+                }
+            }
+        }
+        let idom = idom.into_iter().map(Option::unwrap).collect::<Vec<_>>();
+
+        // A node is in the frontier of every node on the dominator tree path
+        // from each of its predecessors up to, but not including, its own
+        // immediate dominator. The root has no immediate dominator so the
+        // walk includes the root itself.
+        let mut frontiers = vec![vec![]; nodes.len()];
+        for b in 0..nodes.len() {
+            let stop = match b {
+                0 => None,
+                _ => Some(idom[b]),
+            };
+            for &p in &predecessors[b] {
+                let mut runner = p;
+                while Some(runner) != stop {
+                    if !frontiers[runner].contains(&b) {
+                        frontiers[runner].push(b);
+                    }
+                    if runner == 0 {
+                        break;
+                    }
+                    runner = idom[runner];
+                }
+            }
+        }
+
+        let mut children = vec![vec![]; nodes.len()];
+        for b in 1..nodes.len() {
+            children[idom[b]].push(b);
+        }
+        let index = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.key().clone(), i))
+            .collect();
+        let idoms = (1..nodes.len())
+            .map(|b| (nodes[b].key().clone(), nodes[idom[b]].key().clone()))
+            .collect();
+
+        DominatorTree {
+            nodes,
+            index,
+            idom,
+            idoms,
+            children,
+            frontiers,
+        }
+    }
+}
+
+/// Nearest common dominator of `a` and `b` given the immediate dominators
+/// found so far. Nodes are numbered in reverse postorder so a dominator
+/// always has a smaller number than the nodes it dominates.
+fn intersect(idom: &[Option<usize>], mut a: usize, mut b: usize) -> usize {
+    while a != b {
+        while a > b {
+            a = idom[a].unwrap();
+        }
+        while b > a {
+            b = idom[b].unwrap();
+        }
+    }
+    a
+}
+
+/// Result of a dominator search. Holds the immediate dominator and the
+/// dominance frontier of every node reachable from the root. A node `a`
+/// dominates `b` if every path from the root to `b` goes through `a`.
+pub struct DominatorTree<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    nodes: Vec<Node<K, N, E>>,
+    index: HashMap<K, usize>,
+    idom: Vec<usize>,
+    idoms: std::collections::HashMap<K, K>,
+    children: Vec<Vec<usize>>,
+    frontiers: Vec<Vec<usize>>,
+}
+
+impl<K, N, E> DominatorTree<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    /// Returns the root of the search.
+    pub fn root(&self) -> &Node<K, N, E> {
+        &self.nodes[0]
+    }
+
+    /// Returns true if the node with the given key is reachable from the
+    /// root and thus part of the tree.
+    pub fn contains(&self, key: &K) -> bool {
+        self.index.contains_key(key)
+    }
+
+    /// Returns the immediate dominator of the node with the given key or
+    /// `None` if the node is the root or not reachable from it.
+    pub fn idom(&self, key: &K) -> Option<&Node<K, N, E>> {
+        match self.index.get(key) {
+            Some(&i) if i != 0 => Some(&self.nodes[self.idom[i]]),
+            _ => None,
+        }
+    }
+
+    /// Returns the keys of the immediate dominators of all the reachable
+    /// nodes except the root keyed by node key.
+    pub fn idoms(&self) -> &std::collections::HashMap<K, K> {
+        &self.idoms
+    }
+
+    /// Returns true if `a` dominates `b`. Every node dominates itself.
+    /// Returns false if either node is not reachable from the root.
+    pub fn dominates(&self, a: &K, b: &K) -> bool {
+        let (Some(&a), Some(&b)) = (self.index.get(a), self.index.get(b)) else {
+            return false;
+        };
+        let mut b = b;
+        while b > a {
+            b = self.idom[b];
+        }
+        a == b
+    }
+
+    /// Returns an iterator over the strict dominators of the node with the
+    /// given key from its immediate dominator up to the root.
+    pub fn dominators(&self, key: &K) -> impl Iterator<Item = &Node<K, N, E>> + '_ {
+        let start = self.index.get(key).copied();
+        std::iter::successors(start, |&i| (i != 0).then_some(self.idom[i]))
+            .skip(1)
+            .map(|i| &self.nodes[i])
+    }
+
+    /// Returns an iterator over the nodes immediately dominated by the node
+    /// with the given key, its children in the dominator tree.
+    pub fn children(&self, key: &K) -> impl Iterator<Item = &Node<K, N, E>> + '_ {
+        let children = match self.index.get(key) {
+            Some(&i) => &self.children[i][..],
+            None => &[],
+        };
+        children.iter().map(|&i| &self.nodes[i])
+    }
+
+    /// Returns an iterator over the dominance frontier of the node with the
+    /// given key. The frontier holds the nodes where the dominance of the
+    /// node ends: those with a predecessor it dominates that it does not
+    /// strictly dominate themselves.
+    pub fn frontier(&self, key: &K) -> impl Iterator<Item = &Node<K, N, E>> + '_ {
+        let frontier = match self.index.get(key) {
+            Some(&i) => &self.frontiers[i][..],
+            None => &[],
+        };
+        frontier.iter().map(|&i| &self.nodes[i])
+    }
+
+    /// Returns an iterator over the dominator tree in pre-order starting at
+    /// the root. Each node is yielded together with its depth in the tree.
+    pub fn iter(&self) -> DominatorTreeIterator<'_, K, N, E> {
+        DominatorTreeIterator {
+            tree: self,
+            stack: vec![(0, 0)],
+        }
+    }
+}
+
+pub struct DominatorTreeIterator<'a, K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    tree: &'a DominatorTree<K, N, E>,
+    stack: Vec<(usize, usize)>,
+}
+
+impl<'a, K, N, E> Iterator for DominatorTreeIterator<'a, K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    type Item = (&'a Node<K, N, E>, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (i, depth) = self.stack.pop()?;
+        self.stack
+            .extend(self.tree.children[i].iter().rev().map(|&c| (c, depth + 1)));
+        Some((&self.tree.nodes[i], depth))
+    }
+}
@@ -2,6 +2,8 @@ use super::*;
 
 pub type Filter<'a, K, N, E> = &'a mut dyn FnMut(&Edge<K, N, E>) -> bool;
 pub type ForEach<'a, K, N, E> = &'a mut dyn FnMut(&Edge<K, N, E>);
+pub type FilterDepth<'a, K, N, E> = &'a mut dyn FnMut(&Edge<K, N, E>, usize) -> bool;
+pub type ForEachDepth<'a, K, N, E> = &'a mut dyn FnMut(&Edge<K, N, E>, usize);
 
 pub enum Method<'a, K, N, E>
 where
@@ -12,6 +14,8 @@ where
     Empty,
     Filter(Filter<'a, K, N, E>),
     ForEach(ForEach<'a, K, N, E>),
+    FilterDepth(FilterDepth<'a, K, N, E>),
+    ForEachDepth(ForEachDepth<'a, K, N, E>),
 }
 
 impl<'a, K, N, E> Method<'a, K, N, E>
@@ -21,6 +25,12 @@ where
     E: Clone,
 {
     pub fn exec(&mut self, e: &Edge<K, N, E>) -> bool {
+        self.exec_at(e, 0)
+    }
+
+    /// Run the method on an edge whose target lies `depth` edges away from
+    /// the root of the search.
+    pub fn exec_at(&mut self, e: &Edge<K, N, E>, depth: usize) -> bool {
         match self {
             Method::Empty => true,
             Method::ForEach(f) => {
@@ -28,6 +38,11 @@ where
                 true
             }
             Method::Filter(f) => f(e),
+            Method::FilterDepth(f) => f(e, depth),
+            Method::ForEachDepth(f) => {
+                f(e, depth);
+                true
+            }
         }
     }
 }
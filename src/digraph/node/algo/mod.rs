@@ -1,9 +1,17 @@
 use super::*;
 
+pub mod astar;
+pub mod bellman_ford;
 pub mod bfs;
+pub mod bidirectional;
 pub mod dfs;
+pub mod dijkstra;
+pub mod dominators;
 pub mod order;
 pub mod pfs;
+pub mod simple_paths;
+pub mod visitor;
+pub mod yen;
 
 mod method;
-mod path;
+pub mod path;
@@ -1,5 +1,9 @@
 use super::*;
-use std::{fmt::Display, hash::Hash, ops::Index};
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::Index,
+};
 
 pub fn backtrack_edge_tree<K, N, E>(edge_tree: Vec<Edge<K, N, E>>) -> Vec<Edge<K, N, E>>
 where
@@ -50,20 +54,25 @@ where
         self.edges.len() + 1
     }
 
+    pub fn is_empty(&self) -> bool {
+        // A path without edges only contains the root node.
+        self.edges.is_empty()
+    }
+
     pub fn from_edge_tree(edge_tree: Vec<Edge<K, N, E>>) -> Path<K, N, E> {
         Path {
             edges: backtrack_edge_tree(edge_tree),
         }
     }
 
-    pub fn iter_nodes(&self) -> PathNodeIterator<K, N, E> {
+    pub fn iter_nodes(&self) -> PathNodeIterator<'_, K, N, E> {
         PathNodeIterator {
             path: self,
             position: 0,
         }
     }
 
-    pub fn iter_edges(&self) -> PathEdgeIterator<K, N, E> {
+    pub fn iter_edges(&self) -> PathEdgeIterator<'_, K, N, E> {
         PathEdgeIterator {
             path: self,
             position: 0,
@@ -108,6 +117,21 @@ where
     }
 }
 
+impl<K, N, E> Debug for Path<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let keys = self
+            .iter_nodes()
+            .map(|node| node.key().to_string())
+            .collect::<Vec<_>>();
+        write!(f, "Path({})", keys.join(" -> "))
+    }
+}
+
 pub struct PathEdgeIterator<'a, K, N, E>
 where
     K: Clone + Hash + Display + PartialEq + Eq,
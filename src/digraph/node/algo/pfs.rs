@@ -1,23 +1,65 @@
 use super::{method::*, path::*, *};
 use ahash::AHashSet as HashSet;
-use std::{cmp::Reverse, collections::BinaryHeap, fmt::Display, hash::Hash};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    fmt::Display,
+    hash::Hash,
+};
 
 enum Priority {
     Min,
     Max,
 }
 
+/// Nodes waiting to be expanded by the iterator ordered by their value.
+enum Frontier<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    Min(BinaryHeap<Reverse<Node<K, N, E>>>),
+    Max(BinaryHeap<Node<K, N, E>>),
+}
+
+impl<K, N, E> Frontier<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone + Ord,
+    E: Clone,
+{
+    fn push(&mut self, node: Node<K, N, E>) {
+        match self {
+            Frontier::Min(queue) => queue.push(Reverse(node)),
+            Frontier::Max(queue) => queue.push(node),
+        }
+    }
+
+    fn pop(&mut self) -> Option<Node<K, N, E>> {
+        match self {
+            Frontier::Min(queue) => queue.pop().map(|Reverse(node)| node),
+            Frontier::Max(queue) => queue.pop(),
+        }
+    }
+}
+
 pub struct Pfs<'a, K, N, E>
 where
     K: Clone + Hash + Display + PartialEq + Eq,
     N: Clone,
     E: Clone,
 {
-    root: Node<K, N, E>,
+    roots: Vec<Node<K, N, E>>,
     target: Option<K>,
     method: Method<'a, K, N, E>,
     transpose: Transposition,
     priority: Priority,
+    started: bool,
+    frontier: Option<Frontier<K, N, E>>,
+    edges: VecDeque<Edge<K, N, E>>,
+    visited: HashSet<K>,
+    origins: HashMap<K, K>,
 }
 
 impl<'a, K, N, E> Pfs<'a, K, N, E>
@@ -27,12 +69,28 @@ where
     E: Clone,
 {
     pub fn new(root: &Node<K, N, E>) -> Self {
+        Self::from_roots([root.clone()])
+    }
+
+    /// Create a search that starts from all of `roots` at once. The roots
+    /// are put in the priority queue together, so the search always expands
+    /// the best node reached from any of them. Use `origin` to find out
+    /// which root reached a node.
+    pub fn from_roots<I>(roots: I) -> Self
+    where
+        I: IntoIterator<Item = Node<K, N, E>>,
+    {
         Pfs {
-            root: root.clone(),
+            roots: roots.into_iter().collect(),
             target: None,
             method: Method::Empty,
             transpose: Transposition::Outbound,
             priority: Priority::Min,
+            started: false,
+            frontier: None,
+            edges: VecDeque::new(),
+            visited: HashSet::default(),
+            origins: HashMap::new(),
         }
     }
 
@@ -66,208 +124,113 @@ where
         self
     }
 
-    fn loop_outbound_min(
-        &mut self,
-        result: &mut Vec<Edge<K, N, E>>,
-        visited: &mut HashSet<K>,
-        queue: &mut BinaryHeap<Reverse<Node<K, N, E>>>,
-    ) -> bool {
-        while let Some(node) = queue.pop() {
-            let node = node.0;
-            for edge in node.iter_out() {
-                if self.method.exec(&edge) {
-                    let v = edge.1.clone();
-                    if !visited.contains(v.key()) {
-                        visited.insert(v.key().clone());
-                        result.push(edge);
-                        if let Some(ref t) = self.target {
-                            if v.key() == t {
-                                return true;
-                            }
-                        }
-                        queue.push(Reverse(v.clone()));
-                    }
-                }
-            }
+    fn adjacent(&self, node: &Node<K, N, E>) -> VecDeque<Edge<K, N, E>> {
+        match self.transpose {
+            Transposition::Outbound => node.iter_out().collect(),
+            Transposition::Inbound => node.iter_in().map(|edge| edge.reverse()).collect(),
         }
-        false
     }
 
-    fn loop_inbound_min(
-        &mut self,
-        result: &mut Vec<Edge<K, N, E>>,
-        visited: &mut HashSet<K>,
-        queue: &mut BinaryHeap<Reverse<Node<K, N, E>>>,
-    ) -> bool {
-        while let Some(node) = queue.pop() {
-            let node = node.0;
-            for edge in node.iter_out() {
-                let edge = edge.reverse();
-                if self.method.exec(&edge) {
-                    let v = edge.1.clone();
-                    if !visited.contains(v.key()) {
-                        visited.insert(v.key().clone());
-                        result.push(edge);
-                        if let Some(ref t) = self.target {
-                            if v.key() == t {
-                                return true;
-                            }
-                        }
-                        queue.push(Reverse(v.clone()));
-                    }
-                }
-            }
-        }
-        false
+    /// Returns the key of the root from which the node with the given key
+    /// was reached, or `None` if the node has not been visited. A root is
+    /// its own origin.
+    pub fn origin(&self, key: &K) -> Option<&K> {
+        self.origins.get(key)
     }
 
-    fn loop_outbound_max(
-        &mut self,
-        result: &mut Vec<Edge<K, N, E>>,
-        visited: &mut HashSet<K>,
-        queue: &mut BinaryHeap<Node<K, N, E>>,
-    ) -> bool {
-        while let Some(node) = queue.pop() {
-            for edge in node.iter_out() {
-                if self.method.exec(&edge) {
-                    let v = edge.1.clone();
-                    if !visited.contains(v.key()) {
-                        visited.insert(v.key().clone());
-                        result.push(edge);
-                        if let Some(ref t) = self.target {
-                            if v.key() == t {
-                                return true;
-                            }
-                        }
-                        queue.push(v.clone());
-                    }
-                }
-            }
-        }
-        false
+    /// Returns the origin of every node visited so far keyed by node key.
+    pub fn origins(&self) -> &HashMap<K, K> {
+        &self.origins
     }
 
-    fn loop_inbound_max(
-        &mut self,
-        result: &mut Vec<Edge<K, N, E>>,
-        visited: &mut HashSet<K>,
-        queue: &mut BinaryHeap<Node<K, N, E>>,
-    ) -> bool {
-        while let Some(node) = queue.pop() {
-            for edge in node.iter_in() {
-                let edge = edge.reverse();
-                if self.method.exec(&edge) {
-                    let v = edge.1.clone();
-                    if !visited.contains(v.key()) {
-                        visited.insert(v.key().clone());
-                        result.push(edge);
-                        if let Some(ref t) = self.target {
-                            if v.key() == t {
-                                return true;
-                            }
-                        }
-                        queue.push(v.clone());
-                    }
-                }
+    /// Start the traversal over from the roots. When searching for a cycle
+    /// only the first root is used and it is left unvisited so that an edge
+    /// back to it can be found.
+    fn restart(&mut self, cycle: bool) {
+        self.started = true;
+        self.edges.clear();
+        self.visited.clear();
+        self.origins.clear();
+        let mut frontier = match self.priority {
+            Priority::Min => Frontier::Min(BinaryHeap::new()),
+            Priority::Max => Frontier::Max(BinaryHeap::new()),
+        };
+        let count = match cycle {
+            true => 1,
+            false => self.roots.len(),
+        };
+        for root in self.roots.iter().take(count) {
+            let key = root.key().clone();
+            if self.origins.insert(key.clone(), key.clone()).is_none() {
+                frontier.push(root.clone());
+            }
+            if !cycle {
+                self.visited.insert(key);
             }
         }
-        false
+        self.frontier = Some(frontier);
     }
 
     pub fn search(&mut self) -> Option<Node<K, N, E>> {
-        self.search_path()
-            .map(|path| path.last_node().unwrap().clone())
+        self.restart(false);
+        let target = self.target.clone();
+        self.find(|edge| target.as_ref() == Some(edge.target().key()))
+            .map(|edge| edge.target().clone())
     }
 
-    pub fn search_cycle(&'a mut self) -> Option<Path<K, N, E>> {
-        let mut edges = vec![];
-        let mut visited = HashSet::default();
+    pub fn search_cycle(&mut self) -> Option<Path<K, N, E>> {
+        self.target = Some(self.roots.first()?.key().clone());
+        self.restart(true);
+        self.collect_path()
+    }
 
-        self.target = Some(self.root.key().clone());
+    pub fn search_path(&mut self) -> Option<Path<K, N, E>> {
+        self.restart(false);
+        self.collect_path()
+    }
 
-        match self.transpose {
-            Transposition::Outbound => match self.priority {
-                Priority::Min => {
-                    let mut queue = BinaryHeap::new();
-                    queue.push(Reverse(self.root.clone()));
-                    match self.loop_outbound_min(&mut edges, &mut visited, &mut queue) {
-                        true => Some(Path::from_edge_tree(edges)),
-                        false => None,
-                    }
-                }
-                Priority::Max => {
-                    let mut queue = BinaryHeap::new();
-                    queue.push(self.root.clone());
-                    match self.loop_outbound_max(&mut edges, &mut visited, &mut queue) {
-                        true => Some(Path::from_edge_tree(edges)),
-                        false => None,
-                    }
-                }
-            },
-            Transposition::Inbound => match self.priority {
-                Priority::Min => {
-                    let mut queue = BinaryHeap::new();
-                    queue.push(Reverse(self.root.clone()));
-                    match self.loop_outbound_min(&mut edges, &mut visited, &mut queue) {
-                        true => Some(Path::from_edge_tree(edges)),
-                        false => None,
-                    }
-                }
-                Priority::Max => {
-                    let mut queue = BinaryHeap::new();
-                    queue.push(self.root.clone());
-                    match self.loop_outbound_max(&mut edges, &mut visited, &mut queue) {
-                        true => Some(Path::from_edge_tree(edges)),
-                        false => None,
-                    }
-                }
-            },
+    fn collect_path(&mut self) -> Option<Path<K, N, E>> {
+        let target = self.target.clone()?;
+        let edges = self.by_ref().collect::<Vec<_>>();
+        match edges.last() {
+            Some(edge) if edge.target().key() == &target => Some(Path::from_edge_tree(edges)),
+            _ => None,
         }
     }
+}
 
-    pub fn search_path(&mut self) -> Option<Path<K, N, E>> {
-        let mut edges = vec![];
-        let mut visited = HashSet::default();
-
-        visited.insert(self.root.key().clone());
+/// Iterating a `Pfs` yields the edges of the search tree in the order the
+/// search discovers them, expanding the node with the smallest or largest
+/// value first. The iteration stops after the edge that reaches the target,
+/// if one is set.
+impl<'a, K, N, E> Iterator for Pfs<'a, K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone + Ord,
+    E: Clone,
+{
+    type Item = Edge<K, N, E>;
 
-        match self.transpose {
-            Transposition::Outbound => match self.priority {
-                Priority::Min => {
-                    let mut queue = BinaryHeap::new();
-                    queue.push(Reverse(self.root.clone()));
-                    match self.loop_outbound_min(&mut edges, &mut visited, &mut queue) {
-                        true => Some(Path::from_edge_tree(edges)),
-                        false => None,
-                    }
-                }
-                Priority::Max => {
-                    let mut queue = BinaryHeap::new();
-                    queue.push(self.root.clone());
-                    match self.loop_outbound_max(&mut edges, &mut visited, &mut queue) {
-                        true => Some(Path::from_edge_tree(edges)),
-                        false => None,
-                    }
-                }
-            },
-            Transposition::Inbound => match self.priority {
-                Priority::Min => {
-                    let mut queue = BinaryHeap::new();
-                    queue.push(Reverse(self.root.clone()));
-                    match self.loop_inbound_min(&mut edges, &mut visited, &mut queue) {
-                        true => Some(Path::from_edge_tree(edges)),
-                        false => None,
-                    }
-                }
-                Priority::Max => {
-                    let mut queue = BinaryHeap::new();
-                    queue.push(self.root.clone());
-                    match self.loop_inbound_max(&mut edges, &mut visited, &mut queue) {
-                        true => Some(Path::from_edge_tree(edges)),
-                        false => None,
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.restart(false);
+        }
+        loop {
+            while let Some(edge) = self.edges.pop_front() {
+                if self.method.exec(&edge) && self.visited.insert(edge.target().key().clone()) {
+                    let origin = self.origins[edge.source().key()].clone();
+                    self.origins.insert(edge.target().key().clone(), origin);
+                    if self.target.as_ref() == Some(edge.target().key()) {
+                        self.frontier = None;
+                        self.edges.clear();
+                    } else if let Some(frontier) = self.frontier.as_mut() {
+                        frontier.push(edge.target().clone());
                     }
+                    return Some(edge);
                 }
-            },
+            }
+            let node = self.frontier.as_mut()?.pop()?;
+            self.edges = self.adjacent(&node);
         }
     }
 }
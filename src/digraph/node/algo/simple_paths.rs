@@ -0,0 +1,85 @@
+use super::{path::*, *};
+use ahash::AHashSet as HashSet;
+use std::{fmt::Display, hash::Hash};
+
+/// Iterator over the simple paths from a node to a target. The search is a
+/// depth-first search that keeps the nodes of the current path in a set so
+/// that no path visits a node twice. Paths are produced lazily one at a time.
+pub struct SimplePaths<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    target: K,
+    max_depth: Option<usize>,
+    stack: Vec<Vec<Edge<K, N, E>>>,
+    edges: Vec<Edge<K, N, E>>,
+    visited: HashSet<K>,
+}
+
+impl<K, N, E> SimplePaths<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    pub fn new(root: &Node<K, N, E>, target: &K, max_depth: Option<usize>) -> Self {
+        let mut visited = HashSet::default();
+        let mut stack = vec![];
+        if root.key() != target {
+            visited.insert(root.key().clone());
+            stack.push(Self::successors(root));
+        }
+        SimplePaths {
+            target: target.clone(),
+            max_depth,
+            stack,
+            edges: vec![],
+            visited,
+        }
+    }
+
+    /// Outbound edges of `node` in reverse so that they are popped in order.
+    fn successors(node: &Node<K, N, E>) -> Vec<Edge<K, N, E>> {
+        let mut edges = node.iter_out().collect::<Vec<_>>();
+        edges.reverse();
+        edges
+    }
+}
+
+impl<K, N, E> Iterator for SimplePaths<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    type Item = Path<K, N, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let max_depth = self.max_depth.unwrap_or(usize::MAX);
+        while let Some(successors) = self.stack.last_mut() {
+            let Some(edge) = successors.pop() else {
+                self.stack.pop();
+                if let Some(Edge(_, v, _)) = self.edges.pop() {
+                    self.visited.remove(v.key());
+                }
+                continue;
+            };
+            let v = edge.target().clone();
+            if v.key() == &self.target {
+                if self.edges.len() >= max_depth {
+                    continue;
+                }
+                let mut edges = self.edges.clone();
+                edges.push(edge);
+                return Some(Path { edges });
+            }
+            if self.edges.len() + 2 <= max_depth && self.visited.insert(v.key().clone()) {
+                self.edges.push(edge);
+                self.stack.push(Self::successors(&v));
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,89 @@
+use super::*;
+use std::{fmt::Display, hash::Hash};
+
+/// Returned by the callbacks of a `Visitor` to steer the traversal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    /// Carry on with the traversal.
+    Continue,
+    /// Don't search past the node or edge being visited. Returned from
+    /// `discover_node` the node is finished without following its edges.
+    /// Returned from `tree_edge` the target is left undiscovered. Has the
+    /// same effect as `Continue` from the other callbacks.
+    Prune,
+    /// End the traversal.
+    Stop,
+}
+
+/// Callbacks for the events of a depth-first search run by `Dfs::visit`.
+/// Every callback does nothing by default so only the events of interest
+/// need to be implemented.
+///
+/// Each edge the search examines is passed to exactly one of the edge
+/// callbacks. Edges are classified by the state of their target: an edge to
+/// an undiscovered node is a tree edge, an edge to a node that is
+/// discovered but not finished is a back edge and an edge to a finished
+/// node is a forward or cross edge.
+///
+/// # Example
+///
+/// ```
+/// use gdsl::digraph::*;
+///
+/// struct Cyclic(bool);
+///
+/// impl Visitor<char, (), ()> for Cyclic {
+///     fn back_edge(&mut self, _: &Edge<char, (), ()>) -> Control {
+///         self.0 = true;
+///         Control::Stop
+///     }
+/// }
+///
+/// let a = Node::new('A', ());
+/// let b = Node::new('B', ());
+/// let c = Node::new('C', ());
+///
+/// a.connect(&b, ());
+/// b.connect(&c, ());
+///
+/// let mut visitor = Cyclic(false);
+/// a.dfs().visit(&mut visitor);
+/// assert!(!visitor.0);
+///
+/// c.connect(&a, ());
+///
+/// assert!(a.dfs().visit(&mut visitor) == Control::Stop);
+/// assert!(visitor.0);
+/// ```
+pub trait Visitor<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    /// Called when the search first reaches a node.
+    fn discover_node(&mut self, _node: &Node<K, N, E>) -> Control {
+        Control::Continue
+    }
+
+    /// Called when all the edges of a node have been examined.
+    fn finish_node(&mut self, _node: &Node<K, N, E>) -> Control {
+        Control::Continue
+    }
+
+    /// Called for an edge whose target has not been discovered yet, before
+    /// the target is discovered.
+    fn tree_edge(&mut self, _edge: &Edge<K, N, E>) -> Control {
+        Control::Continue
+    }
+
+    /// Called for an edge to a node on the current search path.
+    fn back_edge(&mut self, _edge: &Edge<K, N, E>) -> Control {
+        Control::Continue
+    }
+
+    /// Called for an edge to a node that has already been finished.
+    fn forward_or_cross_edge(&mut self, _edge: &Edge<K, N, E>) -> Control {
+        Control::Continue
+    }
+}
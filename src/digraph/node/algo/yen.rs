@@ -0,0 +1,186 @@
+use super::{path::*, *};
+use crate::visit::Visit;
+use ahash::{AHashMap as HashMap, AHashSet as HashSet};
+use std::{cmp::Ordering, collections::BinaryHeap, fmt::Display, hash::Hash, ops::Add};
+
+/// Edges of a path each paired with its position among the outbound edges of
+/// its source so that parallel edges can be told apart.
+type Route<K, N, E> = Vec<(Edge<K, N, E>, usize)>;
+
+/// Iterator over the loopless paths from a node to a target in order of
+/// increasing cost using Yen's algorithm. Each path is found lazily so
+/// `take(k)` yields the `k` shortest paths. Weights must be non-negative.
+pub struct KShortestPaths<'a, K, N, E, W>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    root: Node<K, N, E>,
+    target: K,
+    weight: &'a dyn Fn(&E) -> W,
+    found: Vec<Route<K, N, E>>,
+    candidates: Vec<(Route<K, N, E>, W)>,
+    seen: HashSet<Vec<(K, usize)>>,
+    started: bool,
+}
+
+impl<'a, K, N, E, W> KShortestPaths<'a, K, N, E, W>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+    W: Copy + PartialOrd + Default + Add<Output = W>,
+{
+    pub fn new(root: &Node<K, N, E>, target: &K, weight: &'a dyn Fn(&E) -> W) -> Self {
+        KShortestPaths {
+            root: root.clone(),
+            target: target.clone(),
+            weight,
+            found: vec![],
+            candidates: vec![],
+            seen: HashSet::default(),
+            started: false,
+        }
+    }
+
+    fn ids(route: &[(Edge<K, N, E>, usize)]) -> Vec<(K, usize)> {
+        route
+            .iter()
+            .map(|(edge, i)| (edge.source().key().clone(), *i))
+            .collect()
+    }
+
+    fn cost(&self, route: &[(Edge<K, N, E>, usize)]) -> W {
+        route.iter().fold(W::default(), |total, (edge, _)| {
+            total + (self.weight)(edge.value())
+        })
+    }
+
+    /// Shortest path from `from` to the target that avoids the given nodes
+    /// and edges.
+    fn spur(
+        &self,
+        from: &Node<K, N, E>,
+        nodes: &HashSet<K>,
+        edges: &HashSet<(K, usize)>,
+    ) -> Option<(Route<K, N, E>, W)> {
+        let mut distances = HashMap::default();
+        let mut predecessors: HashMap<K, (Edge<K, N, E>, usize)> = HashMap::default();
+        let mut settled = HashSet::default();
+        let mut queue = BinaryHeap::new();
+
+        distances.insert(from.key().clone(), W::default());
+        queue.push(Visit(W::default(), from.clone()));
+
+        while let Some(Visit(dist, node)) = queue.pop() {
+            if !settled.insert(node.key().clone()) {
+                continue;
+            }
+            if node.key() == &self.target {
+                let mut route = vec![];
+                let mut current = &self.target;
+                while let Some(step) = predecessors.get(current) {
+                    route.push(step.clone());
+                    current = step.0.source().key();
+                }
+                route.reverse();
+                return Some((route, dist));
+            }
+            for (i, edge) in node.iter_out().enumerate() {
+                let v = edge.target().clone();
+                if settled.contains(v.key())
+                    || nodes.contains(v.key())
+                    || edges.contains(&(node.key().clone(), i))
+                {
+                    continue;
+                }
+                let alt = dist + (self.weight)(edge.value());
+                let shorter = match distances.get(v.key()) {
+                    Some(current) => alt < *current,
+                    None => true,
+                };
+                if shorter {
+                    distances.insert(v.key().clone(), alt);
+                    predecessors.insert(v.key().clone(), (edge, i));
+                    queue.push(Visit(alt, v));
+                }
+            }
+        }
+        None
+    }
+
+    /// Deviate from the last path found at each of its nodes in turn. The
+    /// part before the deviation is kept and the rest is replaced by the
+    /// shortest path that avoids the nodes already used and the edges taken
+    /// at that point by every path found so far with the same beginning.
+    fn deviations(&mut self) {
+        let last = match self.found.last() {
+            Some(last) => last.clone(),
+            None => return,
+        };
+        for i in 0..last.len() {
+            let spur_node = last[i].0.source();
+            let root = &last[..i];
+            let root_ids = Self::ids(root);
+
+            let edges = self
+                .found
+                .iter()
+                .filter(|route| route.len() > i && Self::ids(&route[..i]) == root_ids)
+                .map(|route| (route[i].0.source().key().clone(), route[i].1))
+                .collect::<HashSet<_>>();
+            let nodes = root
+                .iter()
+                .map(|(edge, _)| edge.source().key().clone())
+                .collect::<HashSet<_>>();
+
+            if let Some((spur, cost)) = self.spur(spur_node, &nodes, &edges) {
+                let mut route = root.to_vec();
+                route.extend(spur);
+                if self.seen.insert(Self::ids(&route)) {
+                    let cost = self.cost(root) + cost;
+                    self.candidates.push((route, cost));
+                }
+            }
+        }
+    }
+}
+
+impl<'a, K, N, E, W> Iterator for KShortestPaths<'a, K, N, E, W>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+    W: Copy + PartialOrd + Default + Add<Output = W>,
+{
+    type Item = (Path<K, N, E>, W);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            if self.root.key() == &self.target {
+                return None;
+            }
+            if let Some((route, cost)) =
+                self.spur(&self.root, &HashSet::default(), &HashSet::default())
+            {
+                self.seen.insert(Self::ids(&route));
+                self.candidates.push((route, cost));
+            }
+        } else {
+            self.deviations();
+        }
+
+        let best = (0..self.candidates.len()).reduce(|a, b| {
+            match self.candidates[b].1.partial_cmp(&self.candidates[a].1) {
+                Some(Ordering::Less) => b,
+                _ => a,
+            }
+        })?;
+        let (route, cost) = self.candidates.swap_remove(best);
+        let edges = route.iter().map(|(edge, _)| edge.clone()).collect();
+        self.found.push(route);
+        Some((Path { edges }, cost))
+    }
+}
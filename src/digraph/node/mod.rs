@@ -29,16 +29,37 @@
 //! Node's are wrapped in a reference counted smart pointer. This means
 //! that a node can be cloned and shared among multiple owners.
 //!
+//! A node only holds weak pointers to its adjacent nodes, so dropping the
+//! last pointer to a node leaves dangling edges in the adjacency lists of
+//! its neighbours. Dangling edges are skipped by iterators and lookups and
+//! pruned from an adjacency list the next time it is modified. Call
+//! `isolate` on a node before dropping it to remove its edges eagerly.
+//!
 //! This node uses `Rc` for reference counting, thus it is not thread-safe.
 
 mod adjacent;
 mod algo;
 
-use crate::error::Error;
+use crate::{
+    error::Error,
+    traits::{Directed, Neighbors, NodeRef},
+};
 
+pub use self::algo::{
+    bellman_ford::{BellmanFordError, NegativeCycle},
+    bfs::Bfs,
+    dfs::Dfs,
+    dijkstra::{Dijkstra, ShortestPaths},
+    dominators::{DominatorTree, DominatorTreeIterator, Dominators},
+    path::Path,
+    pfs::Pfs,
+    simple_paths::SimplePaths,
+    visitor::{Control, Visitor},
+    yen::KShortestPaths,
+};
 use self::{
     adjacent::*,
-    algo::{bfs::*, dfs::*, order::*, pfs::*},
+    algo::{astar::*, bellman_ford::*, bidirectional::*, order::*},
 };
 // use anyhow::{anyhow, Result};
 use std::{
@@ -270,7 +291,8 @@ where
     /// Connects this node to another node. The connection is created in both
     /// directions. The connection is created with the given edge value and
     /// defaults to `()`. This function allows for creating multiple
-    /// connections between the same nodes.
+    /// connections between the same nodes. The connection gets the key `0`,
+    /// use `connect_keyed` to tell parallel connections apart by their keys.
     ///
     /// # Example
     ///
@@ -285,15 +307,7 @@ where
     /// assert!(n1.is_connected(n2.key()));
     /// ```
     pub fn connect(&self, other: &Self, value: E) {
-        self.inner
-            .2
-            .borrow_mut()
-            .push_outbound((other.clone(), value.clone()));
-        other
-            .inner
-            .2
-            .borrow_mut()
-            .push_inbound((self.clone(), value));
+        self.attach(other, 0, value);
     }
 
     /// Connects this node to another node. The connection is created in both
@@ -329,6 +343,61 @@ where
         }
     }
 
+    /// Connects this node to another node with a connection identified by the
+    /// given key. Keys are unique among the connections from this node to the
+    /// other node, so parallel connections such as several transport links
+    /// between the same two stations can be told apart. The key `0` is
+    /// reserved for connections made with `connect`, which all share it and
+    /// can't be addressed individually. Returns Ok(()) if the connection was
+    /// created, Err(ReservedKey) if the key is `0` and Err(EdgeAlreadyExists)
+    /// if a connection with the key exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::digraph::*;
+    ///
+    /// let a = Node::new("Helsinki", ());
+    /// let b = Node::new("Tampere", ());
+    ///
+    /// a.connect_keyed(&b, 1, "rail").unwrap();
+    /// a.connect_keyed(&b, 7, "bus").unwrap();
+    ///
+    /// assert!(a.connect_keyed(&b, 7, "coach").is_err());
+    /// assert!(a.connect_keyed(&b, 0, "walk").is_err());
+    /// assert!(a.parallel_edges(b.key()) == vec![(1, "rail"), (7, "bus")]);
+    /// ```
+    pub fn connect_keyed(&self, other: &Self, key: usize, value: E) -> Result<(), Error> {
+        if key == 0 {
+            return Err(Error::ReservedKey);
+        }
+        let exists = self
+            .inner
+            .2
+            .borrow()
+            .parallel_outbound(other.key())
+            .any(|(k, _)| k == key);
+        if exists {
+            Err(Error::EdgeAlreadyExists)
+        } else {
+            self.attach(other, key, value);
+            Ok(())
+        }
+    }
+
+    fn attach(&self, other: &Self, key: usize, value: E) {
+        let value = Rc::new(RefCell::new(value));
+        self.inner
+            .2
+            .borrow_mut()
+            .push_outbound((other.clone(), value.clone(), key));
+        other
+            .inner
+            .2
+            .borrow_mut()
+            .push_inbound((self.clone(), value, key));
+    }
+
     /// Disconnect two nodes from each other. The connection is removed in both
     /// directions. Returns Ok(EdgeValue) if the connection was removed,
     /// Err(()) if the connection doesn't exist.
@@ -356,7 +425,7 @@ where
             Some(other) => match self.inner.2.borrow_mut().remove_outbound(other.key()) {
                 Ok(edge) => {
                     other.inner.2.borrow_mut().remove_inbound(self.key())?;
-                    Ok(edge)
+                    Ok(take_value(edge))
                 }
                 Err(err) => Err(err),
             },
@@ -364,6 +433,157 @@ where
         }
     }
 
+    /// Removes the connection with the given key from this node to another
+    /// node. Parallel connections with other keys are left in place. Returns
+    /// Ok(EdgeValue) if the connection was removed, Err(EdgeNotFound) if no
+    /// such connection exists and Err(ReservedKey) if the key is `0`. The
+    /// connections made with `connect` share that key and are removed with
+    /// `disconnect` instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::digraph::*;
+    ///
+    /// let n1 = Node::new(1, ());
+    /// let n2 = Node::new(2, ());
+    ///
+    /// n1.connect(&n2, 'a');
+    /// n1.connect_keyed(&n2, 1, 'b').unwrap();
+    ///
+    /// assert!(n1.disconnect_edge(&2, 0).is_err());
+    /// assert!(n1.disconnect_edge(&2, 1).unwrap() == 'b');
+    /// assert!(n1.disconnect_edge(&2, 1).is_err());
+    /// assert!(n1.parallel_edges(&2) == vec![(0, 'a')]);
+    /// ```
+    pub fn disconnect_edge(&self, other: &K, key: usize) -> Result<E, Error> {
+        if key == 0 {
+            return Err(Error::ReservedKey);
+        }
+        let node = self.find_outbound(other).ok_or(Error::EdgeNotFound)?;
+        let value = self
+            .inner
+            .2
+            .borrow_mut()
+            .remove_outbound_keyed(other, key)?;
+        node.inner
+            .2
+            .borrow_mut()
+            .remove_inbound_keyed(self.key(), key)?;
+        Ok(take_value(value))
+    }
+
+    /// Returns the keys and values of the connections from this node to another
+    /// node ordered by key. Connections made with `connect` have the key `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::digraph::*;
+    ///
+    /// let n1 = Node::new(1, ());
+    /// let n2 = Node::new(2, ());
+    ///
+    /// n1.connect(&n2, 10);
+    /// n1.connect(&n2, 20);
+    /// n1.connect_keyed(&n2, 3, 30).unwrap();
+    ///
+    /// assert!(n1.parallel_edges(&2) == vec![(0, 10), (0, 20), (3, 30)]);
+    /// assert!(n1.parallel_edges(&3).is_empty());
+    /// ```
+    pub fn parallel_edges(&self, other: &K) -> Vec<(usize, E)> {
+        let mut edges = self
+            .inner
+            .2
+            .borrow()
+            .parallel_outbound(other)
+            .map(|(key, value)| (key, value.borrow().clone()))
+            .collect::<Vec<_>>();
+        edges.sort_by_key(|(key, _)| *key);
+        edges
+    }
+
+    /// Returns the target, key and value of every outbound edge. Serializing
+    /// the edges this way keeps the keys of parallel edges.
+    pub(super) fn outbound_keyed(&self) -> Vec<(Node<K, N, E>, usize, E)> {
+        self.inner
+            .2
+            .borrow()
+            .keyed_outbound()
+            .map(|(node, key, value)| (node, key, value.borrow().clone()))
+            .collect()
+    }
+
+    /// Reads the value of the edge between this node and the node with the
+    /// given key with `f` and returns its result. Outbound edges are searched
+    /// before inbound ones. The value is stored once and shared by both
+    /// endpoints. Returns `None` if the nodes are not connected.
+    ///
+    /// # Panics
+    ///
+    /// The value is borrowed while `f` runs. Calling `update_edge` for the
+    /// same edge from within `f` panics with a `BorrowMutError`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::digraph::*;
+    ///
+    /// let n1 = Node::new(1, ());
+    /// let n2 = Node::new(2, ());
+    ///
+    /// n1.connect(&n2, 4);
+    /// n1.update_edge(&2, |w| *w += 1).unwrap();
+    ///
+    /// assert!(n2.edge_value(&1, |w| *w) == Some(5));
+    /// assert!(n1.edge_value(&2, |w| w * 2) == Some(10));
+    /// assert!(n1.edge_value(&3, |w| *w).is_none());
+    /// ```
+    pub fn edge_value<F, R>(&self, other: &K, f: F) -> Option<R>
+    where
+        F: FnOnce(&E) -> R,
+    {
+        self.find_edge(other).map(|value| f(&value.borrow()))
+    }
+
+    fn find_edge(&self, other: &K) -> Option<Rc<RefCell<E>>> {
+        let adjacent = self.inner.2.borrow();
+        adjacent
+            .find_outbound(other)
+            .or_else(|| adjacent.find_inbound(other))
+            .map(|(_, value)| value.clone())
+    }
+
+    /// Updates the value of the edge between this node and the node with the
+    /// given key in place with `f` and returns its result. Outbound edges are
+    /// searched before inbound ones. Returns `Err(EdgeNotFound)` if the nodes
+    /// are not connected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::digraph::*;
+    ///
+    /// let n1 = Node::new(1, ());
+    /// let n2 = Node::new(2, ());
+    ///
+    /// n1.connect(&n2, 1.0);
+    ///
+    /// n2.update_edge(&1, |flow| *flow *= 0.5).unwrap();
+    ///
+    /// assert!(n1.iter_out().next().unwrap().value() == &0.5);
+    /// assert!(n1.update_edge(&3, |_| ()).is_err());
+    /// ```
+    pub fn update_edge<F, R>(&self, other: &K, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&mut E) -> R,
+    {
+        match self.find_edge(other) {
+            Some(value) => Ok(f(&mut value.borrow_mut())),
+            None => Err(Error::EdgeNotFound),
+        }
+    }
+
     /// Removes all inbound and outbound connections to and from the node.
     ///
     /// # Example
@@ -558,7 +778,7 @@ where
     /// assert!(order[1] == n2);
     /// assert!(order[2] == n3);
     /// ```
-    pub fn preorder(&self) -> Order<K, N, E> {
+    pub fn preorder(&self) -> Order<'_, K, N, E> {
         Order::preorder(self)
     }
 
@@ -585,7 +805,7 @@ where
     /// assert!(order[1] == n2);
     /// assert!(order[0] == n3);
     /// ```
-    pub fn postorder(&self) -> Order<K, N, E> {
+    pub fn postorder(&self) -> Order<'_, K, N, E> {
         Order::postroder(self)
     }
 
@@ -617,7 +837,7 @@ where
     /// assert!(iter.next().unwrap() == n2);
     /// assert!(iter.next().unwrap() == n3);
     /// ```
-    pub fn dfs(&self) -> Dfs<K, N, E> {
+    pub fn dfs(&self) -> Dfs<'_, K, N, E> {
         Dfs::new(self)
     }
 
@@ -650,7 +870,7 @@ where
     /// assert!(iter.next().unwrap() == n2);
     /// assert!(iter.next().unwrap() == n3);
     /// ```
-    pub fn bfs(&self) -> Bfs<K, N, E> {
+    pub fn bfs(&self) -> Bfs<'_, K, N, E> {
         Bfs::new(self)
     }
 
@@ -682,13 +902,265 @@ where
     /// assert!(path[0] == Edge(n1, n3.clone(), ()));
     /// assert!(path[1] == Edge(n3, n4, ()));
     ///```
-    pub fn pfs(&self) -> Pfs<K, N, E>
+    pub fn pfs(&self) -> Pfs<'_, K, N, E>
     where
         N: Ord,
     {
         Pfs::new(self)
     }
 
+    /// Returns a search-object that computes shortest paths from this node
+    /// using Dijkstra's algorithm. Edge weights are computed from the edge
+    /// values by the given closure and the distances are kept in a side
+    /// table, so node values don't need to be ordered or mutable. Weights
+    /// must be non-negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::digraph::*;
+    ///
+    /// let n1 = Node::new('A', ());
+    /// let n2 = Node::new('B', ());
+    /// let n3 = Node::new('C', ());
+    ///
+    /// n1.connect(&n2, 5);
+    /// n1.connect(&n3, 1);
+    /// n3.connect(&n2, 2);
+    ///
+    /// let paths = n1.dijkstra(&|e| *e).search();
+    ///
+    /// assert!(paths.distance(&'B') == Some(3));
+    ///
+    /// let path = paths.path_to(&'B').unwrap();
+    ///
+    /// assert!(path[0] == Edge(n1, n3.clone(), 1));
+    /// assert!(path[1] == Edge(n3, n2, 2));
+    /// ```
+    pub fn dijkstra<'a, W>(&self, weight: &'a dyn Fn(&E) -> W) -> Dijkstra<'a, K, N, E, W>
+    where
+        W: Copy + PartialOrd + Default + std::ops::Add<Output = W>,
+    {
+        Dijkstra::new(self, weight)
+    }
+
+    /// Returns a search-object that finds a path from this node to `target`
+    /// by searching forward from this node over outbound edges and backward
+    /// from the target over inbound edges until the two searches meet. Use
+    /// `search_path` for the path with the fewest edges or
+    /// `search_weighted` for the cheapest path by a weight closure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::digraph::*;
+    ///
+    /// let n = (0..5).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+    ///
+    /// n[0].connect(&n[1], 1);
+    /// n[1].connect(&n[4], 9);
+    /// n[0].connect(&n[2], 1);
+    /// n[2].connect(&n[3], 1);
+    /// n[3].connect(&n[4], 1);
+    ///
+    /// let path = n[0].bidirectional(&n[4]).search_path().unwrap();
+    ///
+    /// assert!(path.to_vec_nodes() == vec![n[0].clone(), n[1].clone(), n[4].clone()]);
+    ///
+    /// let (path, cost) = n[0].bidirectional(&n[4]).search_weighted(&|e| *e).unwrap();
+    ///
+    /// assert!(cost == 3);
+    /// assert!(path.len() == 4);
+    /// ```
+    pub fn bidirectional(&self, target: &Node<K, N, E>) -> Bidirectional<'_, K, N, E> {
+        Bidirectional::new(self, target)
+    }
+
+    /// Returns a search-object that finds the cheapest path from this node
+    /// to a target using the A* algorithm. The cost of an edge is computed by
+    /// `cost` and `heuristic` estimates the remaining cost from a node to the
+    /// target. The heuristic must not overestimate the remaining cost.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::digraph::*;
+    ///
+    /// // Nodes on a line keyed by their position.
+    /// let n = (0..5).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+    ///
+    /// n[0].connect(&n[1], 1);
+    /// n[1].connect(&n[2], 1);
+    /// n[2].connect(&n[4], 2);
+    /// n[0].connect(&n[3], 3);
+    /// n[3].connect(&n[4], 9);
+    ///
+    /// let (path, cost) = n[0]
+    ///     .astar(&|Edge(_, _, e)| *e, &|node| 4 - *node.key())
+    ///     .target(&4)
+    ///     .search_path()
+    ///     .unwrap();
+    ///
+    /// assert!(cost == 4);
+    /// assert!(path.to_vec_nodes() == vec![n[0].clone(), n[1].clone(), n[2].clone(), n[4].clone()]);
+    /// ```
+    pub fn astar<'a, W>(
+        &self,
+        cost: &'a dyn Fn(&Edge<K, N, E>) -> W,
+        heuristic: &'a dyn Fn(&Node<K, N, E>) -> W,
+    ) -> AStar<'a, K, N, E, W>
+    where
+        W: Copy + PartialOrd + Default + std::ops::Add<Output = W>,
+    {
+        AStar::new(self, cost, heuristic)
+    }
+
+    /// Returns a search-object that computes shortest paths from this node
+    /// using the Bellman-Ford algorithm. Unlike `dijkstra`, edge weights
+    /// computed by `weight` may be negative. The search fails with a
+    /// `NegativeCycle` if a negative cycle is reachable from the node.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::digraph::*;
+    ///
+    /// let n1 = Node::new('A', ());
+    /// let n2 = Node::new('B', ());
+    /// let n3 = Node::new('C', ());
+    ///
+    /// n1.connect(&n2, 4);
+    /// n1.connect(&n3, 2);
+    /// n2.connect(&n3, -3);
+    ///
+    /// let paths = n1.bellman_ford(&|e| *e).search().unwrap();
+    ///
+    /// assert!(paths.distance(&'C') == Some(1));
+    ///
+    /// n3.connect(&n2, 1);
+    ///
+    /// assert!(n1.bellman_ford(&|e| *e).search().is_err());
+    /// ```
+    pub fn bellman_ford<'a, W>(&self, weight: &'a dyn Fn(&E) -> W) -> BellmanFord<'a, K, N, E, W>
+    where
+        W: Copy + PartialOrd + Default + std::ops::Add<Output = W>,
+    {
+        BellmanFord::new(self, weight)
+    }
+
+    /// Returns an iterator over every simple path from this node to the
+    /// target. A simple path visits each node at most once. Paths with more
+    /// than `max_depth` edges are skipped, which keeps the search usable on
+    /// large graphs. Parallel edges produce separate paths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::digraph::*;
+    ///
+    /// let n = (0..4).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+    ///
+    /// n[0].connect(&n[1], ());
+    /// n[0].connect(&n[2], ());
+    /// n[1].connect(&n[2], ());
+    /// n[1].connect(&n[3], ());
+    /// n[2].connect(&n[3], ());
+    ///
+    /// let paths = n[0].all_simple_paths(&3, None).collect::<Vec<_>>();
+    ///
+    /// assert!(paths.len() == 3);
+    ///
+    /// let short = n[0].all_simple_paths(&3, Some(2)).collect::<Vec<_>>();
+    ///
+    /// assert!(short.len() == 2);
+    /// assert!(short.iter().all(|path| path.len() == 3));
+    /// ```
+    pub fn all_simple_paths(&self, target: &K, max_depth: Option<usize>) -> SimplePaths<K, N, E> {
+        SimplePaths::new(self, target, max_depth)
+    }
+
+    /// Returns an iterator over the loopless paths from this node to the
+    /// target in order of increasing cost using Yen's algorithm. The weight
+    /// of each edge is computed by `weight` and must be non-negative. Paths
+    /// are found lazily, use `take(k)` to get the `k` shortest paths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::digraph::*;
+    ///
+    /// let a = Node::new('A', ());
+    /// let b = Node::new('B', ());
+    /// let c = Node::new('C', ());
+    /// let d = Node::new('D', ());
+    ///
+    /// a.connect(&b, 1);
+    /// a.connect(&c, 2);
+    /// b.connect(&d, 5);
+    /// c.connect(&d, 1);
+    /// b.connect(&c, 1);
+    ///
+    /// let paths = a.k_shortest_paths(&'D', &|e| *e).take(3).collect::<Vec<_>>();
+    ///
+    /// assert!(paths.iter().map(|(_, cost)| *cost).collect::<Vec<_>>() == vec![3, 3, 6]);
+    /// assert!(paths[2].0.to_vec_nodes() == vec![a, b, d]);
+    /// ```
+    pub fn k_shortest_paths<'a, W>(
+        &self,
+        target: &K,
+        weight: &'a dyn Fn(&E) -> W,
+    ) -> KShortestPaths<'a, K, N, E, W>
+    where
+        W: Copy + PartialOrd + Default + std::ops::Add<Output = W>,
+    {
+        KShortestPaths::new(self, target, weight)
+    }
+
+    /// Returns a search-object that computes the dominator tree of the nodes
+    /// reachable from this node. A node `a` dominates `b` if every path from
+    /// this node to `b` goes through `a`. Use `transpose` to follow inbound
+    /// edges instead, which gives the post-dominators when this node is the
+    /// exit of a control-flow graph.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::digraph::*;
+    ///
+    /// // An if-else inside a loop.
+    /// let entry = Node::new("entry", ());
+    /// let head = Node::new("head", ());
+    /// let then = Node::new("then", ());
+    /// let other = Node::new("else", ());
+    /// let join = Node::new("join", ());
+    /// let exit = Node::new("exit", ());
+    ///
+    /// entry.connect(&head, ());
+    /// head.connect(&then, ());
+    /// head.connect(&other, ());
+    /// then.connect(&join, ());
+    /// other.connect(&join, ());
+    /// join.connect(&head, ());
+    /// head.connect(&exit, ());
+    ///
+    /// let tree = entry.dominators().search();
+    /// let idoms: &std::collections::HashMap<&str, &str> = tree.idoms();
+    ///
+    /// assert!(tree.idom(&"join") == Some(&head));
+    /// assert!(idoms[&"exit"] == "head");
+    /// assert!(tree.dominates(&"head", &"then"));
+    /// assert!(tree.frontier(&"then").collect::<Vec<_>>() == vec![&join]);
+    /// assert!(tree.frontier(&"join").collect::<Vec<_>>() == vec![&head]);
+    ///
+    /// let post = exit.dominators().transpose().search();
+    ///
+    /// assert!(post.idom(&"then") == Some(&join));
+    /// assert!(post.idom(&"entry") == Some(&head));
+    /// ```
+    pub fn dominators(&self) -> Dominators<K, N, E> {
+        Dominators::new(self)
+    }
+
     /// Returns an iterator over the node's outbound edges.
     ///
     /// # Example
@@ -707,7 +1179,7 @@ where
     /// assert!(iter.next().unwrap() == Edge(n1.clone(), n2.clone(), ()));
     /// assert!(iter.next().unwrap() == Edge(n1, n3, ()));
     /// ```
-    pub fn iter_out(&self) -> IterOut<K, N, E> {
+    pub fn iter_out(&self) -> IterOut<'_, K, N, E> {
         IterOut {
             node: self,
             position: 0,
@@ -733,7 +1205,7 @@ where
     /// assert!(iter.next().unwrap() == Edge(n1.clone(), n2.clone(), ()));
     /// assert!(iter.next().is_none());
     /// ```
-    pub fn iter_in(&self) -> IterIn<K, N, E> {
+    pub fn iter_in(&self) -> IterIn<'_, K, N, E> {
         IterIn {
             node: self,
             position: 0,
@@ -788,7 +1260,7 @@ where
     E: Clone,
 {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.value().cmp(other.value()))
+        Some(self.cmp(other))
     }
 }
 
@@ -822,21 +1294,14 @@ where
     type Item = Edge<K, N, E>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.node.inner.2.borrow().get_outbound(self.position) {
-            Some(current) => match current.0.upgrade() {
-                Some(node) => {
-                    self.position += 1;
-                    Some(Edge(self.node.clone(), node, current.1.clone()))
-                }
-                None => {
-                    panic!(
-                        "Target node in the adjacency list of `node = {}` has been dropped.",
-                        self.node.key()
-                    );
-                }
-            },
-            None => None,
+        let adjacent = self.node.inner.2.borrow();
+        while let Some(current) = adjacent.get_outbound(self.position) {
+            self.position += 1;
+            if let Some(node) = current.0.upgrade() {
+                return Some(Edge(self.node.clone(), node, current.1.borrow().clone()));
+            }
         }
+        None
     }
 }
 
@@ -859,21 +1324,14 @@ where
     type Item = Edge<K, N, E>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.node.inner.2.borrow().get_inbound(self.position) {
-            Some(current) => match current.0.upgrade() {
-                Some(node) => {
-                    self.position += 1;
-                    Some(Edge(node, self.node.clone(), current.1.clone()))
-                }
-                None => {
-                    panic!(
-                        "Target node in the adjacency list of `node = {}` has been dropped.",
-                        self.node.key()
-                    );
-                }
-            },
-            None => None,
+        let adjacent = self.node.inner.2.borrow();
+        while let Some(current) = adjacent.get_inbound(self.position) {
+            self.position += 1;
+            if let Some(node) = current.0.upgrade() {
+                return Some(Edge(node, self.node.clone(), current.1.borrow().clone()));
+            }
         }
+        None
     }
 }
 
@@ -893,3 +1351,49 @@ where
         }
     }
 }
+
+impl<K, N, E> NodeRef for Node<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    type Key = K;
+    type Value = N;
+    type EdgeValue = E;
+
+    fn key(&self) -> &K {
+        self.key()
+    }
+
+    fn value(&self) -> &N {
+        self.value()
+    }
+
+    fn sizeof(&self) -> usize {
+        self.sizeof()
+    }
+}
+
+impl<K, N, E> Neighbors for Node<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    fn successors(&self) -> impl Iterator<Item = (Self, E)> + '_ {
+        self.iter_out().map(|Edge(_, v, e)| (v, e))
+    }
+
+    fn predecessors(&self) -> impl Iterator<Item = (Self, E)> + '_ {
+        self.iter_in().map(|Edge(u, _, e)| (u, e))
+    }
+}
+
+impl<K, N, E> Directed for Node<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+}
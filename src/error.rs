@@ -6,4 +6,14 @@ pub enum Error {
 	EdgeNotFound,
 	#[error("Connection already exists")]
 	EdgeAlreadyExists,
+	#[error("Edge key 0 is reserved for connections without a key")]
+	ReservedKey,
+	#[error("Node not found")]
+	NodeNotFound,
+	#[error("Negative cycle found")]
+	NegativeCycle,
+	#[error("Cycle found")]
+	Cycle,
+	#[error("Odd cycle found")]
+	OddCycle,
 }
@@ -10,21 +10,25 @@
 //! - Directed and undirected graph and node types.
 //!
 //! - Normal and sync versions of the node and graph types. Normally a node is
-//! wrapped in a `Rc` pointer and adjacent edges in a `RefCell`. In the sync
-//! versions these are `Arc` and `RwLock` respectively.
+//!   wrapped in a `Rc` pointer and adjacent edges in a `RefCell`. In the sync
+//!   versions these are `Arc` and `RwLock` respectively.
 //!
 //! - Nodes implement building blocks for algorithms in the form of breadth-first,
-//! depth-firs and priority-first traversals as well as post- and preordering.
+//!   depth-firs and priority-first traversals as well as post- and preordering.
+//!
+//! - Traits shared by the node and graph types of all four modules, so that
+//!   an algorithm can be written once for `Rc` and `Arc` nodes alike, and
+//!   generic traversals built on them.
 //!
 //! - Macros for creating inline graphs in an easy-to-read style.
 //!
 //! - Graphs implement Serde's serialization and deserialization.
 //!
 //! - Removing or inserting connections or otherwise manipulating the graph
-//! or any of its nodes is stable. Any references to nodes or edges remain
-//! consistent. This is due to not relying on an underlying container where
-//! nodes and edges would be represented as separate lists and indexed into,
-//! in GDSL a node "owns" all it's incoming and outgoing connections.
+//!   or any of its nodes is stable. Any references to nodes or edges remain
+//!   consistent. This is due to not relying on an underlying container where
+//!   nodes and edges would be represented as separate lists and indexed into,
+//!   in GDSL a node "owns" all it's incoming and outgoing connections.
 //!
 //! Motivation for creating this library has been to explore the idea of graphs and
 //! connected nodes as more generic data-structures that store data without
@@ -103,5 +107,7 @@
 pub mod digraph;
 pub mod sync_digraph;
 pub mod sync_ungraph;
+pub mod traits;
 pub mod ungraph;
 pub mod error;
+mod visit;
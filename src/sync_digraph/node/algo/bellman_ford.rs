@@ -0,0 +1,227 @@
+use super::{dijkstra::ShortestPaths, method::*, path::*, *};
+use crate::error::Error;
+use ahash::{AHashMap as HashMap, AHashSet as HashSet};
+use std::{
+    collections::VecDeque,
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::Add,
+};
+use thiserror::Error;
+
+/// A cycle of negative total weight. Going around the cycle once more always
+/// makes a path shorter, so shortest paths through it are not defined. It
+/// converts into `Error::NegativeCycle` for callers that don't need the cycle.
+#[derive(Error)]
+#[error("Negative cycle found")]
+pub struct NegativeCycle<K, N, E>(pub Path<K, N, E>)
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone;
+
+impl<K, N, E> Debug for NegativeCycle<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("NegativeCycle").field(&self.0).finish()
+    }
+}
+
+/// Errors returned by `Graph::bellman_ford`. They convert into
+/// `Error::NodeNotFound` and `Error::NegativeCycle`.
+#[derive(Error)]
+pub enum BellmanFordError<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    #[error("Source node not found")]
+    SourceNotFound,
+    #[error(transparent)]
+    NegativeCycle(#[from] NegativeCycle<K, N, E>),
+}
+
+impl<K, N, E> Debug for BellmanFordError<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BellmanFordError::SourceNotFound => f.write_str("SourceNotFound"),
+            BellmanFordError::NegativeCycle(cycle) => {
+                f.debug_tuple("NegativeCycle").field(cycle).finish()
+            }
+        }
+    }
+}
+
+impl<K, N, E> From<NegativeCycle<K, N, E>> for Error
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    fn from(_: NegativeCycle<K, N, E>) -> Self {
+        Error::NegativeCycle
+    }
+}
+
+impl<K, N, E> From<BellmanFordError<K, N, E>> for Error
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    fn from(err: BellmanFordError<K, N, E>) -> Self {
+        match err {
+            BellmanFordError::SourceNotFound => Error::NodeNotFound,
+            BellmanFordError::NegativeCycle(cycle) => cycle.into(),
+        }
+    }
+}
+
+pub struct BellmanFord<'a, K, N, E, W>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    root: Node<K, N, E>,
+    weight: &'a dyn Fn(&E) -> W,
+    method: Method<'a, K, N, E>,
+    transpose: Transposition,
+}
+
+impl<'a, K, N, E, W> BellmanFord<'a, K, N, E, W>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+    W: Copy + PartialOrd + Default + Add<Output = W>,
+{
+    pub fn new(root: &Node<K, N, E>, weight: &'a dyn Fn(&E) -> W) -> Self {
+        BellmanFord {
+            root: root.clone(),
+            weight,
+            method: Method::Empty,
+            transpose: Transposition::Outbound,
+        }
+    }
+
+    pub fn transpose(mut self) -> Self {
+        self.transpose = Transposition::Inbound;
+        self
+    }
+
+    pub fn for_each(mut self, f: ForEach<'a, K, N, E>) -> Self {
+        self.method = Method::ForEach(f);
+        self
+    }
+
+    pub fn filter(mut self, f: Filter<'a, K, N, E>) -> Self {
+        self.method = Method::Filter(f);
+        self
+    }
+
+    /// Collects the edges reachable from the root. The method is executed
+    /// once for each edge.
+    fn reachable_edges(&mut self) -> (usize, Vec<Edge<K, N, E>>) {
+        let mut result = vec![];
+        let mut queue = VecDeque::new();
+        let mut visited = HashSet::default();
+
+        queue.push_back(self.root.clone());
+        visited.insert(self.root.key().clone());
+
+        while let Some(node) = queue.pop_front() {
+            let edges: Vec<Edge<K, N, E>> = match self.transpose {
+                Transposition::Outbound => node.iter_out().collect(),
+                Transposition::Inbound => node.iter_in().map(|edge| edge.reverse()).collect(),
+            };
+            for edge in edges {
+                if self.method.exec(&edge) {
+                    if visited.insert(edge.1.key().clone()) {
+                        queue.push_back(edge.1.clone());
+                    }
+                    result.push(edge);
+                }
+            }
+        }
+        (visited.len(), result)
+    }
+
+    /// Computes the shortest paths from the root to every reachable node.
+    /// Edge weights may be negative. If a negative cycle is reachable from
+    /// the root, it is returned as a `NegativeCycle`.
+    pub fn search(&mut self) -> Result<ShortestPaths<K, N, E, W>, NegativeCycle<K, N, E>> {
+        let (len, edges) = self.reachable_edges();
+        let mut distances = HashMap::default();
+        let mut predecessors: HashMap<K, Edge<K, N, E>> = HashMap::default();
+
+        distances.insert(self.root.key().clone(), W::default());
+
+        for _ in 1..len {
+            let mut relaxed = false;
+            for edge in &edges {
+                if let Some(alt) = self.relax(&distances, edge) {
+                    distances.insert(edge.1.key().clone(), alt);
+                    predecessors.insert(edge.1.key().clone(), edge.clone());
+                    relaxed = true;
+                }
+            }
+            if !relaxed {
+                return Ok(ShortestPaths::new(
+                    self.root.clone(),
+                    distances,
+                    predecessors,
+                ));
+            }
+        }
+
+        for edge in &edges {
+            if self.relax(&distances, edge).is_some() {
+                predecessors.insert(edge.1.key().clone(), edge.clone());
+
+                // Walking back `len` steps is guaranteed to end up on the
+                // cycle itself.
+                let mut start = edge.1.key().clone();
+                for _ in 0..len {
+                    start = predecessors[&start].0.key().clone();
+                }
+                let mut cycle = vec![];
+                let mut current = &start;
+                loop {
+                    let edge = &predecessors[current];
+                    cycle.push(edge.clone());
+                    current = edge.0.key();
+                    if current == &start {
+                        break;
+                    }
+                }
+                cycle.reverse();
+                return Err(NegativeCycle(Path { edges: cycle }));
+            }
+        }
+        Ok(ShortestPaths::new(
+            self.root.clone(),
+            distances,
+            predecessors,
+        ))
+    }
+
+    fn relax(&self, distances: &HashMap<K, W>, edge: &Edge<K, N, E>) -> Option<W> {
+        let alt = *distances.get(edge.0.key())? + (self.weight)(&edge.2);
+        match distances.get(edge.1.key()) {
+            Some(current) if alt < *current => Some(alt),
+            Some(_) => None,
+            None => Some(alt),
+        }
+    }
+}
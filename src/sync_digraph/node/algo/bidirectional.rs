@@ -0,0 +1,224 @@
+use super::{method::*, path::*, *};
+use crate::visit::Visit;
+use ahash::{AHashMap as HashMap, AHashSet as HashSet};
+use std::{collections::BinaryHeap, fmt::Display, hash::Hash, ops::Add};
+
+/// Point-to-point search that runs from the source over outbound edges and
+/// from the target over inbound edges at the same time and joins the two
+/// halves where they meet. Only the neighbourhoods of the two ends up to
+/// half the distance between them are explored.
+///
+/// Edges are passed to the `for_each` and `filter` closures in their
+/// original direction by both halves of the search.
+pub struct Bidirectional<'a, K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    source: Node<K, N, E>,
+    target: Node<K, N, E>,
+    method: Method<'a, K, N, E>,
+}
+
+impl<'a, K, N, E> Bidirectional<'a, K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    pub fn new(source: &Node<K, N, E>, target: &Node<K, N, E>) -> Self {
+        Bidirectional {
+            source: source.clone(),
+            target: target.clone(),
+            method: Method::Empty,
+        }
+    }
+
+    pub fn for_each(mut self, f: ForEach<'a, K, N, E>) -> Self {
+        self.method = Method::ForEach(f);
+        self
+    }
+
+    pub fn filter(mut self, f: Filter<'a, K, N, E>) -> Self {
+        self.method = Method::Filter(f);
+        self
+    }
+
+    /// Edges of `node` in the direction of the search together with the
+    /// node at their far end.
+    fn adjacent(
+        &mut self,
+        node: &Node<K, N, E>,
+        transpose: &Transposition,
+    ) -> Vec<(Edge<K, N, E>, Node<K, N, E>)> {
+        let edges: Vec<Edge<K, N, E>> = match transpose {
+            Transposition::Outbound => node.iter_out().collect(),
+            Transposition::Inbound => node.iter_in().collect(),
+        };
+        edges
+            .into_iter()
+            .filter(|edge| self.method.exec(edge))
+            .map(|edge| {
+                let next = match transpose {
+                    Transposition::Outbound => edge.target().clone(),
+                    Transposition::Inbound => edge.source().clone(),
+                };
+                (edge, next)
+            })
+            .collect()
+    }
+
+    /// Join the predecessor edges of the forward search and the successor
+    /// edges of the backward search at the meeting node.
+    fn join(
+        &self,
+        meet: &K,
+        predecessors: &HashMap<K, Edge<K, N, E>>,
+        successors: &HashMap<K, Edge<K, N, E>>,
+    ) -> Path<K, N, E> {
+        let mut edges = vec![];
+        let mut current = meet;
+        while let Some(edge) = predecessors.get(current) {
+            edges.push(edge.clone());
+            current = edge.source().key();
+        }
+        edges.reverse();
+        let mut current = meet;
+        while let Some(edge) = successors.get(current) {
+            edges.push(edge.clone());
+            current = edge.target().key();
+        }
+        Path { edges }
+    }
+
+    /// Find a path with the fewest edges from the source to the target with
+    /// a breadth-first search from both ends. The search expands a whole
+    /// level of the smaller frontier at a time. Returns `None` if the target
+    /// is not reachable or is the source itself.
+    pub fn search_path(&mut self) -> Option<Path<K, N, E>> {
+        if self.source.key() == self.target.key() {
+            return None;
+        }
+        let mut halves = [
+            (Transposition::Outbound, vec![self.source.clone()]),
+            (Transposition::Inbound, vec![self.target.clone()]),
+        ];
+        let mut depths = [HashMap::default(), HashMap::default()];
+        let mut edges = [HashMap::default(), HashMap::default()];
+        depths[0].insert(self.source.key().clone(), 0);
+        depths[1].insert(self.target.key().clone(), 0);
+
+        while !halves[0].1.is_empty() && !halves[1].1.is_empty() {
+            let side = match halves[0].1.len() <= halves[1].1.len() {
+                true => 0,
+                false => 1,
+            };
+            let frontier = std::mem::take(&mut halves[side].1);
+            let mut meet: Option<(K, usize)> = None;
+            for node in frontier {
+                let depth = depths[side][node.key()] + 1;
+                for (edge, next) in self.adjacent(&node, &halves[side].0) {
+                    if depths[side].contains_key(next.key()) {
+                        continue;
+                    }
+                    depths[side].insert(next.key().clone(), depth);
+                    edges[side].insert(next.key().clone(), edge);
+                    if let Some(other) = depths[1 - side].get(next.key()) {
+                        if meet.as_ref().is_none_or(|(_, len)| depth + other < *len) {
+                            meet = Some((next.key().clone(), depth + other));
+                        }
+                    }
+                    halves[side].1.push(next);
+                }
+            }
+            if let Some((meet, _)) = meet {
+                return Some(self.join(&meet, &edges[0], &edges[1]));
+            }
+        }
+        None
+    }
+
+    /// Find the cheapest path from the source to the target with Dijkstra's
+    /// algorithm run from both ends. The weight of each edge is computed by
+    /// `weight` and must be non-negative. Returns the path and its cost or
+    /// `None` if the target is not reachable or is the source itself.
+    pub fn search_weighted<W>(&mut self, weight: &dyn Fn(&E) -> W) -> Option<(Path<K, N, E>, W)>
+    where
+        W: Copy + PartialOrd + Default + Add<Output = W>,
+    {
+        if self.source.key() == self.target.key() {
+            return None;
+        }
+        let transpose = [Transposition::Outbound, Transposition::Inbound];
+        let mut queues = [BinaryHeap::new(), BinaryHeap::new()];
+        let mut distances = [HashMap::default(), HashMap::default()];
+        let mut edges = [HashMap::default(), HashMap::default()];
+        let mut settled: [HashSet<K>; 2] = [HashSet::default(), HashSet::default()];
+        for (side, node) in [&self.source, &self.target].into_iter().enumerate() {
+            distances[side].insert(node.key().clone(), W::default());
+            queues[side].push(Visit(W::default(), node.clone()));
+        }
+        let mut best: Option<(K, W)> = None;
+
+        loop {
+            // Drop entries of nodes that were already settled so that the
+            // top of each queue is the true minimum of its half.
+            for side in 0..2 {
+                while let Some(top) = queues[side].peek() {
+                    match settled[side].contains(top.1.key()) {
+                        true => queues[side].pop(),
+                        false => break,
+                    };
+                }
+            }
+            let (Some(forward), Some(backward)) = (queues[0].peek(), queues[1].peek()) else {
+                break;
+            };
+            // Every path through an unsettled node costs at least the sum of
+            // the two minimums, so the best meeting found so far is final.
+            if let Some((_, cost)) = &best {
+                if forward.0 + backward.0 >= *cost {
+                    break;
+                }
+            }
+            let side = match forward.0 <= backward.0 {
+                true => 0,
+                false => 1,
+            };
+            let Visit(dist, node) = queues[side].pop().unwrap();
+            settled[side].insert(node.key().clone());
+
+            for (edge, next) in self.adjacent(&node, &transpose[side]) {
+                if settled[side].contains(next.key()) {
+                    continue;
+                }
+                let alt = dist + weight(edge.value());
+                let shorter = match distances[side].get(next.key()) {
+                    Some(current) => alt < *current,
+                    None => true,
+                };
+                if !shorter {
+                    continue;
+                }
+                distances[side].insert(next.key().clone(), alt);
+                edges[side].insert(next.key().clone(), edge);
+                if let Some(&other) = distances[1 - side].get(next.key()) {
+                    let cost = alt + other;
+                    if best.as_ref().is_none_or(|(_, best)| cost < *best) {
+                        best = Some((next.key().clone(), cost));
+                    }
+                }
+                queues[side].push(Visit(alt, next));
+            }
+        }
+
+        let (meet, _) = best?;
+        let path = self.join(&meet, &edges[0], &edges[1]);
+        let cost = path
+            .edges
+            .iter()
+            .fold(W::default(), |total, edge| total + weight(edge.value()));
+        Some((path, cost))
+    }
+}
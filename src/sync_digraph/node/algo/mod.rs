@@ -1,9 +1,13 @@
 use super::*;
 
+pub mod bellman_ford;
 pub mod bfs;
+pub mod bidirectional;
 pub mod dfs;
+pub mod dijkstra;
 pub mod order;
 pub mod pfs;
+pub mod visitor;
 
 mod method;
-mod path;
+pub mod path;
@@ -2,8 +2,10 @@ use super::*;
 
 pub mod bfs;
 pub mod dfs;
+pub mod dijkstra;
 pub mod order;
 pub mod pfs;
+pub mod visitor;
 
 mod method;
-mod path;
+pub mod path;
@@ -1,6 +1,6 @@
 //==== Includes ===============================================================
 
-use std::{fmt::Display, hash::Hash};
+use std::{collections::VecDeque, fmt::Display, hash::Hash};
 
 use ahash::HashSet;
 
@@ -23,6 +23,8 @@ where
     root: &'a Node<K, N, E>,
     method: Method<'a, K, N, E>,
     order: Ordering,
+    stack: Vec<(Option<Edge<K, N, E>>, VecDeque<Edge<K, N, E>>)>,
+    visited: HashSet<K>,
 }
 
 impl<'a, K, N, E> Order<'a, K, N, E>
@@ -36,6 +38,8 @@ where
             root,
             method: Method::Empty,
             order: Ordering::Pre,
+            stack: vec![],
+            visited: HashSet::default(),
         }
     }
 
@@ -59,6 +63,10 @@ where
         self
     }
 
+    fn adjacent(&self, node: &Node<K, N, E>) -> VecDeque<Edge<K, N, E>> {
+        node.iter().collect()
+    }
+
     pub fn search_nodes(&mut self) -> Vec<Node<K, N, E>> {
         let mut nodes = vec![];
         let mut edges = vec![];
@@ -145,3 +153,40 @@ where
         false
     }
 }
+
+/// Iterating an `Order` yields the edges of the depth-first search tree. In
+/// pre-order an edge is yielded as soon as its target is visited, in
+/// post-order once every node below its target has been visited.
+impl<'a, K, N, E> Iterator for Order<'a, K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    type Item = Edge<K, N, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.visited.is_empty() {
+            self.visited.insert(self.root.key().clone());
+            self.stack.push((None, self.adjacent(self.root)));
+        }
+        loop {
+            let Some(edge) = self.stack.last_mut()?.1.pop_front() else {
+                match self.stack.pop() {
+                    Some((Some(edge), _)) => return Some(edge),
+                    _ => continue,
+                }
+            };
+            if self.method.exec(&edge) && self.visited.insert(edge.target().key().clone()) {
+                let edges = self.adjacent(edge.target());
+                match self.order {
+                    Ordering::Pre => {
+                        self.stack.push((None, edges));
+                        return Some(edge);
+                    }
+                    Ordering::Post => self.stack.push((Some(edge), edges)),
+                }
+            }
+        }
+    }
+}
@@ -29,12 +29,21 @@
 //! Node's are wrapped in a reference counted smart pointer. This means
 //! that a node can be cloned and shared among multiple owners.
 //!
+//! A node only holds weak pointers to its adjacent nodes, so dropping the
+//! last pointer to a node leaves dangling edges in the adjacency lists of
+//! its neighbours. Dangling edges are skipped by iterators and lookups and
+//! pruned from an adjacency list the next time it is modified. Call
+//! `isolate` on a node before dropping it to remove its edges eagerly.
+//!
 //! This node uses `Arc` for reference counting, thus it is thread-safe.
 
 mod adjacent;
 mod algo;
 
-use crate::error::Error;
+use crate::{
+    error::Error,
+    traits::{Neighbors, NodeRef},
+};
 use std::{
     fmt::Display,
     hash::Hash,
@@ -42,10 +51,15 @@ use std::{
     sync::{Arc, RwLock, Weak},
 };
 
-use self::{
-    adjacent::*,
-    algo::{bfs::*, dfs::*, order::*, pfs::*},
+pub use self::algo::{
+    bfs::Bfs,
+    dfs::Dfs,
+    dijkstra::{Dijkstra, ShortestPaths},
+    path::Path,
+    pfs::Pfs,
+    visitor::{Control, Visitor},
 };
+use self::{adjacent::*, algo::order::*};
 
 /// An edge between nodes is a tuple struct `Edge(u, v, e)` where `u` is the
 /// source node, `v` is the target node, and `e` is the edge's value.
@@ -240,10 +254,17 @@ where
         self.inner.2.read().unwrap().len_outbound() + self.inner.2.read().unwrap().len_inbound()
     }
 
+    /// Returns the amount of connections created by this node. These are
+    /// iterated before the connections created by adjacent nodes.
+    pub(super) fn len_outbound(&self) -> usize {
+        self.inner.2.read().unwrap().len_outbound()
+    }
+
     /// Connects this node to another node. The connection is created in both
     /// directions. The connection is created with the given edge value and
     /// defaults to `()`. This function allows for creating multiple
-    /// connections between the same nodes.
+    /// connections between the same nodes. The connection gets the key `0`,
+    /// use `connect_keyed` to tell parallel connections apart by their keys.
     ///
     /// # Example
     ///
@@ -258,17 +279,7 @@ where
     /// assert!(n1.is_connected(n2.key()));
     /// ```
     pub fn connect(&self, other: &Self, value: E) {
-        self.inner
-            .2
-            .write()
-            .unwrap()
-            .push_outbound((other.clone(), value.clone()));
-        other
-            .inner
-            .2
-            .write()
-            .unwrap()
-            .push_inbound((self.clone(), value));
+        self.attach(other, 0, value);
     }
 
     /// Connects this node to another node. The connection is created in both
@@ -304,6 +315,64 @@ where
         }
     }
 
+    /// Connects this node to another node with a connection identified by the
+    /// given key. Keys are unique among the connections between the two nodes,
+    /// so parallel connections such as several transport links between the same
+    /// two stations can be told apart. The key `0` is reserved for connections
+    /// made with `connect`, which all share it and can't be addressed
+    /// individually. Returns Ok(()) if the connection was created,
+    /// Err(ReservedKey) if the key is `0` and Err(EdgeAlreadyExists) if a
+    /// connection with the key exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::sync_ungraph::*;
+    ///
+    /// let a = Node::new("Helsinki", ());
+    /// let b = Node::new("Tampere", ());
+    ///
+    /// a.connect_keyed(&b, 1, "rail").unwrap();
+    /// a.connect_keyed(&b, 7, "bus").unwrap();
+    ///
+    /// assert!(a.connect_keyed(&b, 7, "coach").is_err());
+    /// assert!(a.connect_keyed(&b, 0, "walk").is_err());
+    /// assert!(a.parallel_edges(b.key()) == vec![(1, "rail"), (7, "bus")]);
+    /// ```
+    pub fn connect_keyed(&self, other: &Self, key: usize, value: E) -> Result<(), Error> {
+        if key == 0 {
+            return Err(Error::ReservedKey);
+        }
+        let exists = {
+            let adjacent = self.inner.2.read().unwrap();
+            let mut edges = adjacent
+                .parallel_outbound(other.key())
+                .chain(adjacent.parallel_inbound(other.key()));
+            edges.any(|(k, _)| k == key)
+        };
+        if exists {
+            Err(Error::EdgeAlreadyExists)
+        } else {
+            self.attach(other, key, value);
+            Ok(())
+        }
+    }
+
+    fn attach(&self, other: &Self, key: usize, value: E) {
+        let value = Arc::new(RwLock::new(value));
+        self.inner
+            .2
+            .write()
+            .unwrap()
+            .push_outbound((other.clone(), value.clone(), key));
+        other
+            .inner
+            .2
+            .write()
+            .unwrap()
+            .push_inbound((self.clone(), value, key));
+    }
+
     /// Disconnect two nodes from each other. The connection is removed in both
     /// directions. Returns Ok(EdgeValue) if the connection was removed, Err(())
     /// if the connection doesn't exist.
@@ -327,7 +396,187 @@ where
     /// assert!(!n1.is_connected(n2.key()));
     /// ```
     pub fn disconnect(&self, other: &K) -> Result<E, Error> {
-        self.inner.2.write().unwrap().remove_undirected(other)
+        let node = self.find_adjacent(other).ok_or(Error::EdgeNotFound)?;
+        let value = self.inner.2.write().unwrap().remove_undirected(other)?;
+        node.inner.2.write().unwrap().remove_shared(&value)?;
+        Ok(take_value(value))
+    }
+
+    /// Removes the connection with the given key between this node and another
+    /// node. Parallel connections with other keys are left in place. Returns
+    /// Ok(EdgeValue) if the connection was removed, Err(EdgeNotFound) if no
+    /// such connection exists and Err(ReservedKey) if the key is `0`. The
+    /// connections made with `connect` share that key and are removed with
+    /// `disconnect` instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::sync_ungraph::*;
+    ///
+    /// let n1 = Node::new(1, ());
+    /// let n2 = Node::new(2, ());
+    ///
+    /// n1.connect(&n2, 'a');
+    /// n1.connect_keyed(&n2, 1, 'b').unwrap();
+    ///
+    /// assert!(n1.disconnect_edge(&2, 0).is_err());
+    /// assert!(n1.disconnect_edge(&2, 1).unwrap() == 'b');
+    /// assert!(n1.disconnect_edge(&2, 1).is_err());
+    /// assert!(n1.parallel_edges(&2) == vec![(0, 'a')]);
+    /// ```
+    pub fn disconnect_edge(&self, other: &K, key: usize) -> Result<E, Error> {
+        if key == 0 {
+            return Err(Error::ReservedKey);
+        }
+        let node = self.find_adjacent(other).ok_or(Error::EdgeNotFound)?;
+        let outbound = self
+            .inner
+            .2
+            .write()
+            .unwrap()
+            .remove_outbound_keyed(other, key);
+        let value = match outbound {
+            Ok(value) => {
+                node.inner
+                    .2
+                    .write()
+                    .unwrap()
+                    .remove_inbound_keyed(self.key(), key)?;
+                value
+            }
+            Err(_) => {
+                let value = self
+                    .inner
+                    .2
+                    .write()
+                    .unwrap()
+                    .remove_inbound_keyed(other, key)?;
+                node.inner
+                    .2
+                    .write()
+                    .unwrap()
+                    .remove_outbound_keyed(self.key(), key)?;
+                value
+            }
+        };
+        Ok(take_value(value))
+    }
+
+    /// Returns the keys and values of the connections between this node and
+    /// another node ordered by key. Connections made with `connect` have the
+    /// key `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::sync_ungraph::*;
+    ///
+    /// let n1 = Node::new(1, ());
+    /// let n2 = Node::new(2, ());
+    ///
+    /// n1.connect(&n2, 10);
+    /// n1.connect(&n2, 20);
+    /// n1.connect_keyed(&n2, 3, 30).unwrap();
+    ///
+    /// assert!(n1.parallel_edges(&2) == vec![(0, 10), (0, 20), (3, 30)]);
+    /// assert!(n1.parallel_edges(&3).is_empty());
+    /// ```
+    pub fn parallel_edges(&self, other: &K) -> Vec<(usize, E)> {
+        let adjacent = self.inner.2.read().unwrap();
+        // A loop is in both adjacency lists of the node so it is taken once.
+        let inbound = adjacent
+            .parallel_inbound(other)
+            .filter(|_| other != self.key());
+        let mut edges = adjacent
+            .parallel_outbound(other)
+            .chain(inbound)
+            .map(|(key, value)| (key, value.read().unwrap().clone()))
+            .collect::<Vec<_>>();
+        edges.sort_by_key(|(key, _)| *key);
+        edges
+    }
+
+    /// Returns the target, key and value of every outbound edge. Serializing
+    /// the edges this way keeps the keys of parallel edges.
+    pub(super) fn outbound_keyed(&self) -> Vec<(Node<K, N, E>, usize, E)> {
+        self.inner
+            .2
+            .read()
+            .unwrap()
+            .keyed_outbound()
+            .map(|(node, key, value)| (node, key, value.read().unwrap().clone()))
+            .collect()
+    }
+
+    /// Reads the value of the edge between this node and the node with the
+    /// given key with `f` and returns its result. The value is stored once and
+    /// shared by both endpoints. Returns `None` if the nodes are not
+    /// connected.
+    ///
+    /// # Panics
+    ///
+    /// The value is locked while `f` runs. Calling `update_edge` for the same
+    /// edge from within `f` may deadlock or panic.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::sync_ungraph::*;
+    ///
+    /// let n1 = Node::new(1, ());
+    /// let n2 = Node::new(2, ());
+    ///
+    /// n1.connect(&n2, 4);
+    /// n1.update_edge(&2, |w| *w += 1).unwrap();
+    ///
+    /// assert!(n2.edge_value(&1, |w| *w) == Some(5));
+    /// assert!(n1.edge_value(&2, |w| w * 2) == Some(10));
+    /// assert!(n1.edge_value(&3, |w| *w).is_none());
+    /// ```
+    pub fn edge_value<F, R>(&self, other: &K, f: F) -> Option<R>
+    where
+        F: FnOnce(&E) -> R,
+    {
+        self.find_edge(other).map(|value| f(&value.read().unwrap()))
+    }
+
+    fn find_edge(&self, other: &K) -> Option<Arc<RwLock<E>>> {
+        self.inner
+            .2
+            .read()
+            .unwrap()
+            .find_adjacent(other)
+            .map(|(_, value)| value.clone())
+    }
+
+    /// Updates the value of the edge between this node and the node with the
+    /// given key in place with `f` and returns its result. Returns
+    /// `Err(EdgeNotFound)` if the nodes are not connected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gdsl::sync_ungraph::*;
+    ///
+    /// let n1 = Node::new(1, ());
+    /// let n2 = Node::new(2, ());
+    ///
+    /// n1.connect(&n2, 1.0);
+    ///
+    /// n2.update_edge(&1, |flow| *flow *= 0.5).unwrap();
+    ///
+    /// assert!(n1.iter().next().unwrap().value() == &0.5);
+    /// assert!(n1.update_edge(&3, |_| ()).is_err());
+    /// ```
+    pub fn update_edge<F, R>(&self, other: &K, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&mut E) -> R,
+    {
+        match self.find_edge(other) {
+            Some(value) => Ok(f(&mut value.write().unwrap())),
+            None => Err(Error::EdgeNotFound),
+        }
     }
 
     /// Removes all inbound and outbound connections to and from the node.
@@ -404,34 +653,44 @@ where
     /// Returns an iterator-like object that can be used to map, filter and
     /// collect reachable nodes or edges in different orderings such as
     /// postorder or preorder.
-    pub fn order(&self) -> Order<K, N, E> {
+    pub fn order(&self) -> Order<'_, K, N, E> {
         Order::new(self)
     }
 
     /// Returns an iterator-like object that can be used to map, filter,
     /// search and collect nodes or edges resulting from a depth-first search.
-    pub fn dfs(&self) -> Dfs<K, N, E> {
+    pub fn dfs(&self) -> Dfs<'_, K, N, E> {
         Dfs::new(self)
     }
 
     /// Returns an iterator-like object that can be used to map, filter,
     /// search and collect nodes or edges resulting from a breadth-first search.
-    pub fn bfs(&self) -> Bfs<K, N, E> {
+    pub fn bfs(&self) -> Bfs<'_, K, N, E> {
         Bfs::new(self)
     }
 
     /// Returns an iterator-like object that can be used to map, filter,
     /// search and collect nodes or edges resulting from a
     /// priotity-first search.
-    pub fn pfs(&self) -> Pfs<K, N, E>
+    pub fn pfs(&self) -> Pfs<'_, K, N, E>
     where
         N: Ord,
     {
         Pfs::new(self)
     }
 
+    /// Returns a search-object that computes shortest paths from this node
+    /// using Dijkstra's algorithm. Distances are kept in a side table and
+    /// edge weights are computed from the edge values by `weight`.
+    pub fn dijkstra<'a, W>(&self, weight: &'a dyn Fn(&E) -> W) -> Dijkstra<'a, K, N, E, W>
+    where
+        W: Copy + PartialOrd + Default + std::ops::Add<Output = W>,
+    {
+        Dijkstra::new(self, weight)
+    }
+
     /// Returns an iterator over the node's adjacent edges.
-    pub fn iter(&self) -> NodeIterator<K, N, E> {
+    pub fn iter(&self) -> NodeIterator<'_, K, N, E> {
         NodeIterator {
             node: self,
             position: 0,
@@ -487,7 +746,7 @@ where
     E: Clone,
 {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.value().cmp(other.value()))
+        Some(self.cmp(other))
     }
 }
 
@@ -522,13 +781,13 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         let adjacent = &self.node.inner.2.read().unwrap();
-        match adjacent.get_adjacent(self.position) {
-            Some((n, e)) => {
-                self.position += 1;
-                Some(Edge(self.node.clone(), n.upgrade().unwrap(), e.clone()))
+        while let Some((n, e)) = adjacent.get_adjacent(self.position) {
+            self.position += 1;
+            if let Some(n) = n.upgrade() {
+                return Some(Edge(self.node.clone(), n, e.read().unwrap().clone()));
             }
-            None => None,
         }
+        None
     }
 }
 
@@ -548,3 +807,41 @@ where
         }
     }
 }
+
+impl<K, N, E> NodeRef for Node<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    type Key = K;
+    type Value = N;
+    type EdgeValue = E;
+
+    fn key(&self) -> &K {
+        self.key()
+    }
+
+    fn value(&self) -> &N {
+        self.value()
+    }
+
+    fn sizeof(&self) -> usize {
+        self.sizeof()
+    }
+}
+
+impl<K, N, E> Neighbors for Node<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    fn successors(&self) -> impl Iterator<Item = (Self, E)> + '_ {
+        self.iter().map(|Edge(_, v, e)| (v, e))
+    }
+
+    fn predecessors(&self) -> impl Iterator<Item = (Self, E)> + '_ {
+        self.successors()
+    }
+}
@@ -0,0 +1,153 @@
+//! # Graph Traits
+//!
+//! The `digraph`, `ungraph`, `sync_digraph` and `sync_ungraph` modules each
+//! have their own node and graph types. The traits in this module describe
+//! what those types have in common so that an algorithm can be written once
+//! and used with `Rc` and `Arc` based nodes alike.
+//!
+//! - `NodeRef` is a handle to a node with a key and a value.
+//! - `Neighbors` gives access to the nodes adjacent to a node.
+//! - `Directed` marks the node and graph types whose edges have a direction.
+//! - `GraphBase` is a container of nodes looked up by key.
+//!
+//! # Example
+//!
+//! ```
+//! use gdsl::traits::*;
+//!
+//! // Count the edges leaving the nodes of any of the graph types.
+//! fn edge_count<G: GraphBase>(g: &G) -> usize {
+//!     g.nodes().map(|node| node.successors().count()).sum()
+//! }
+//!
+//! let g1 = gdsl::digraph![
+//!     (usize)
+//!     (0) => [1, 2]
+//!     (1) => [2]
+//!     (2) => []
+//! ];
+//! let g2 = gdsl::sync_digraph![
+//!     (usize)
+//!     (0) => [1, 2]
+//!     (1) => [2]
+//!     (2) => []
+//! ];
+//!
+//! assert!(edge_count(&g1) == 3);
+//! assert!(edge_count(&g2) == 3);
+//! ```
+
+use std::{
+    fmt::{Display, Write},
+    hash::Hash,
+};
+
+/// A handle to a node. Cloning the handle is cheap and the clone refers to
+/// the same node.
+pub trait NodeRef: Clone {
+    type Key: Clone + Hash + Display + PartialEq + Eq;
+    type Value: Clone;
+    type EdgeValue: Clone;
+
+    /// Returns the key of the node.
+    fn key(&self) -> &Self::Key;
+
+    /// Returns the value of the node.
+    fn value(&self) -> &Self::Value;
+
+    /// Returns the size of the node and its adjacency lists in bytes.
+    fn sizeof(&self) -> usize;
+}
+
+/// Access to the nodes adjacent to a node.
+///
+/// For a directed node the successors are the targets of its outbound edges
+/// and the predecessors are the sources of its inbound edges. For an
+/// undirected node both are all of its adjacent nodes.
+pub trait Neighbors: NodeRef {
+    /// Returns an iterator over the nodes this node has an edge to together
+    /// with the values of the edges.
+    fn successors(&self) -> impl Iterator<Item = (Self, Self::EdgeValue)> + '_;
+
+    /// Returns an iterator over the nodes that have an edge to this node
+    /// together with the values of the edges.
+    fn predecessors(&self) -> impl Iterator<Item = (Self, Self::EdgeValue)> + '_;
+}
+
+/// Marks the node and graph types whose edges have a direction. Algorithms
+/// that are only defined for directed graphs, such as a topological sort,
+/// can require it.
+pub trait Directed {}
+
+/// A container of nodes looked up by key.
+pub trait GraphBase {
+    type Node: Neighbors;
+
+    /// Returns the node with the given key.
+    fn node(&self, key: &<Self::Node as NodeRef>::Key) -> Option<Self::Node>;
+
+    /// Returns the amount of nodes in the graph.
+    fn node_count(&self) -> usize;
+
+    /// Returns an iterator over the nodes of the graph in arbitrary order.
+    fn nodes(&self) -> impl Iterator<Item = Self::Node> + '_;
+
+    /// Returns true if the graph has a node with the given key.
+    fn contains_node(&self, key: &<Self::Node as NodeRef>::Key) -> bool {
+        self.node(key).is_some()
+    }
+
+    /// Returns the size of the nodes and keys of the graph in bytes.
+    fn sizeof(&self) -> usize {
+        self.nodes()
+            .map(|node| node.sizeof() + std::mem::size_of_val(node.key()))
+            .sum()
+    }
+
+    /// Returns the graph in the Graphviz dot format. The closures return
+    /// the attributes of the graph, each node and each edge.
+    fn to_dot_with_attr(
+        &self,
+        gattr: &dyn Fn(&Self) -> Option<Vec<(String, String)>>,
+        nattr: &dyn Fn(&Self::Node) -> Option<Vec<(String, String)>>,
+        eattr: &dyn Fn(
+            &Self::Node,
+            &Self::Node,
+            &<Self::Node as NodeRef>::EdgeValue,
+        ) -> Option<Vec<(String, String)>>,
+    ) -> String {
+        let mut s = String::new();
+        s.push_str("digraph {\n");
+        if let Some(gattrs) = gattr(self) {
+            for (k, v) in gattrs {
+                s.push_str(&format!("\t{}=\"{}\"\n", k, v));
+            }
+        }
+        for node in self.nodes() {
+            s.push_str(&format!("\t{}", node.key()));
+            if let Some(nattr) = nattr(&node) {
+                s.push_str(&format!(" {}", fmt_attr(nattr)));
+            }
+            s.push('\n');
+        }
+        for u in self.nodes() {
+            for (v, e) in u.successors() {
+                s.push_str(&format!("\t{} -> {}", u.key(), v.key()));
+                if let Some(eattrs) = eattr(&u, &v, &e) {
+                    s.push_str(&format!(" {}", fmt_attr(eattrs)));
+                }
+                s.push('\n');
+            }
+        }
+        s.push('}');
+        s
+    }
+}
+
+fn fmt_attr(attrs: Vec<(String, String)>) -> String {
+    let mut s = String::new();
+    for (k, v) in attrs {
+        write!(&mut s, "[{}=\"{}\"]", k, v).unwrap();
+    }
+    s
+}
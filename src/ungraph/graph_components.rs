@@ -0,0 +1,296 @@
+use super::*;
+
+/// Result of a single depth-first pass over the graph that finds bridges,
+/// articulation points and biconnected components together.
+struct Lowpoints<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    bridges: Vec<Edge<K, N, E>>,
+    articulation_points: Vec<Node<K, N, E>>,
+    biconnected_components: Vec<Vec<Edge<K, N, E>>>,
+}
+
+/// A node on the depth-first stack together with the position of the next
+/// adjacent edge to visit.
+struct Frame {
+    node: usize,
+    parent: Option<(usize, usize)>,
+    position: usize,
+    children: usize,
+    edges: usize,
+}
+
+impl<K, N, E> Graph<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    /// Assign each node a dense index and collect the adjacency lists between
+    /// nodes in the graph. Both ends of an edge get the same edge id so that
+    /// parallel edges can be told apart. Self loops and edges pointing
+    /// outside of the graph are left out.
+    pub(super) fn index_adjacent(
+        &self,
+    ) -> (Vec<Node<K, N, E>>, Vec<Vec<(usize, usize, Edge<K, N, E>)>>) {
+        let nodes = self.nodes.values().cloned().collect::<Vec<_>>();
+        let index: HashMap<K, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.key().clone(), i))
+            .collect();
+
+        // The n:th connection from `u` to `v` is the n:th outbound edge of
+        // `u` to `v` and the n:th inbound edge of `v` from `u`.
+        let mut ids: HashMap<(usize, usize, usize), usize> = HashMap::default();
+        let mut adjacent = vec![];
+        for (u, node) in nodes.iter().enumerate() {
+            let outbound = node.len_outbound();
+            let mut seen: HashMap<(usize, usize), usize> = HashMap::default();
+            let mut list = vec![];
+            for (position, edge) in node.iter().enumerate() {
+                let v = match index.get(edge.1.key()) {
+                    Some(&v) if v != u => v,
+                    _ => continue,
+                };
+                let (s, t) = if position < outbound { (u, v) } else { (v, u) };
+                let count = seen.entry((s, t)).or_insert(0);
+                let next = ids.len();
+                let id = *ids.entry((s, t, *count)).or_insert(next);
+                *count += 1;
+                list.push((v, id, edge));
+            }
+            adjacent.push(list);
+        }
+        (nodes, adjacent)
+    }
+
+    /// Partition the nodes of the graph into connected components. Two nodes
+    /// are in the same component if there is a path between them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = ungraph![
+    ///     (usize)
+    ///     (0) => [1, 2]
+    ///     (1) => []
+    ///     (2) => []
+    ///     (3) => [4]
+    ///     (4) => []
+    ///     (5) => []
+    /// ];
+    ///
+    /// let mut components = g.connected_components();
+    ///
+    /// components.sort_by_key(|c| c.len());
+    ///
+    /// assert!(components.len() == 3);
+    /// assert!(components[0] == vec![g[5].clone()]);
+    /// assert!(components[1].len() == 2);
+    /// assert!(components[2].len() == 3);
+    /// ```
+    pub fn connected_components(&self) -> Vec<Vec<Node<K, N, E>>> {
+        let (nodes, adjacent) = self.index_adjacent();
+        let mut visited = vec![false; nodes.len()];
+        let mut components = vec![];
+
+        for root in 0..nodes.len() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut component = vec![];
+            let mut stack = vec![root];
+            while let Some(u) = stack.pop() {
+                component.push(nodes[u].clone());
+                for &(v, _, _) in &adjacent[u] {
+                    if !visited[v] {
+                        visited[v] = true;
+                        stack.push(v);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// Find the bridges of the graph. A bridge is an edge whose removal
+    /// increases the number of connected components. Parallel edges between
+    /// the same pair of nodes are never bridges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = ungraph![
+    ///     (char)
+    ///     ('A') => ['B', 'C']
+    ///     ('B') => ['C']
+    ///     ('C') => ['D']
+    ///     ('D') => []
+    /// ];
+    ///
+    /// let bridges = g.bridges();
+    ///
+    /// assert!(bridges.len() == 1);
+    /// assert!(bridges[0].source() == &g['C'] || bridges[0].target() == &g['C']);
+    /// assert!(bridges[0].source() == &g['D'] || bridges[0].target() == &g['D']);
+    /// ```
+    pub fn bridges(&self) -> Vec<Edge<K, N, E>> {
+        self.lowpoints().bridges
+    }
+
+    /// Find the articulation points of the graph. An articulation point is a
+    /// node whose removal increases the number of connected components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = ungraph![
+    ///     (char)
+    ///     ('A') => ['B', 'C']
+    ///     ('B') => ['C']
+    ///     ('C') => ['D']
+    ///     ('D') => []
+    /// ];
+    ///
+    /// assert!(g.articulation_points() == vec![g['C'].clone()]);
+    /// ```
+    pub fn articulation_points(&self) -> Vec<Node<K, N, E>> {
+        self.lowpoints().articulation_points
+    }
+
+    /// Partition the edges of the graph into biconnected components. The
+    /// nodes of a biconnected component stay connected if any single one of
+    /// them is removed. Components share articulation points but never
+    /// edges. Self loops are not part of any component.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = ungraph![
+    ///     (char)
+    ///     ('A') => ['B', 'C']
+    ///     ('B') => ['C']
+    ///     ('C') => ['D']
+    ///     ('D') => []
+    /// ];
+    ///
+    /// let mut components = g.biconnected_components();
+    ///
+    /// components.sort_by_key(|c| c.len());
+    ///
+    /// assert!(components.len() == 2);
+    /// assert!(components[0].len() == 1);
+    /// assert!(components[1].len() == 3);
+    /// ```
+    pub fn biconnected_components(&self) -> Vec<Vec<Edge<K, N, E>>> {
+        self.lowpoints().biconnected_components
+    }
+
+    /// Iterative version of the Hopcroft-Tarjan algorithm. Each undirected
+    /// edge is seen from both of its ends, only the tree edge itself is
+    /// skipped when looking back at the parent so that parallel edges count
+    /// as cycles.
+    fn lowpoints(&self) -> Lowpoints<K, N, E> {
+        let (nodes, adjacent) = self.index_adjacent();
+        let mut discovered: Vec<Option<usize>> = vec![None; nodes.len()];
+        let mut low = vec![0; nodes.len()];
+        let mut is_articulation = vec![false; nodes.len()];
+        let mut edge_stack = vec![];
+        let mut time = 0;
+        let mut result = Lowpoints {
+            bridges: vec![],
+            articulation_points: vec![],
+            biconnected_components: vec![],
+        };
+
+        for root in 0..nodes.len() {
+            if discovered[root].is_some() {
+                continue;
+            }
+            discovered[root] = Some(time);
+            low[root] = time;
+            time += 1;
+            let mut frames = vec![Frame {
+                node: root,
+                parent: None,
+                position: 0,
+                children: 0,
+                edges: 0,
+            }];
+
+            while let Some(frame) = frames.last_mut() {
+                let u = frame.node;
+                if let Some((v, id, edge)) = adjacent[u].get(frame.position) {
+                    frame.position += 1;
+                    let v = *v;
+                    if frame.parent.is_some_and(|(_, parent)| parent == *id) {
+                        continue;
+                    }
+                    match discovered[v] {
+                        None => {
+                            frame.children += 1;
+                            discovered[v] = Some(time);
+                            low[v] = time;
+                            time += 1;
+                            edge_stack.push(edge.clone());
+                            frames.push(Frame {
+                                node: v,
+                                parent: Some((u, *id)),
+                                position: 0,
+                                children: 0,
+                                edges: edge_stack.len() - 1,
+                            });
+                        }
+                        Some(d) if d < discovered[u].unwrap() => {
+                            low[u] = low[u].min(d);
+                            edge_stack.push(edge.clone());
+                        }
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+
+                let frame = frames.pop().unwrap();
+                let Some((p, _)) = frame.parent else {
+                    if frame.children > 1 {
+                        is_articulation[u] = true;
+                    }
+                    continue;
+                };
+                low[p] = low[p].min(low[u]);
+                let dp = discovered[p].unwrap();
+                if low[u] > dp {
+                    result.bridges.push(edge_stack[frame.edges].clone());
+                }
+                if low[u] >= dp {
+                    if frames.len() > 1 {
+                        is_articulation[p] = true;
+                    }
+                    let component = edge_stack.drain(frame.edges..).collect();
+                    result.biconnected_components.push(component);
+                }
+            }
+        }
+
+        result.articulation_points = nodes
+            .into_iter()
+            .zip(is_articulation)
+            .filter_map(|(node, is)| is.then_some(node))
+            .collect();
+        result
+    }
+}
@@ -0,0 +1,483 @@
+use super::*;
+use crate::error::Error;
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    ops::{Add, Sub},
+};
+use thiserror::Error;
+
+/// A cycle of odd length found in a graph that was required to be
+/// bipartite. It converts into `Error::OddCycle` for callers that don't need
+/// the cycle.
+#[derive(Error)]
+#[error("Odd cycle found")]
+pub struct OddCycle<K, N, E>(pub Path<K, N, E>)
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone;
+
+impl<K, N, E> Debug for OddCycle<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("OddCycle").field(&self.0).finish()
+    }
+}
+
+impl<K, N, E> From<OddCycle<K, N, E>> for Error
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    fn from(_: OddCycle<K, N, E>) -> Self {
+        Error::OddCycle
+    }
+}
+
+/// Bipartite graph with the nodes of both sides indexed separately and the
+/// edges listed from the left side.
+struct Sides<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    left: usize,
+    right: usize,
+    edges: Vec<(usize, usize, Edge<K, N, E>)>,
+    adjacent: Vec<Vec<usize>>,
+}
+
+impl<K, N, E> Graph<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    /// Returns true if the nodes of the graph can be split into two sets so
+    /// that every edge connects nodes from different sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let mut g = ungraph![
+    ///     (char)
+    ///     ('A') => ['B']
+    ///     ('B') => ['C']
+    ///     ('C') => ['D']
+    ///     ('D') => []
+    /// ];
+    ///
+    /// assert!(g.is_bipartite());
+    ///
+    /// g['A'].connect(&g['C'], ());
+    ///
+    /// assert!(!g.is_bipartite());
+    /// ```
+    pub fn is_bipartite(&self) -> bool {
+        self.bipartition().is_ok()
+    }
+
+    /// Split the nodes of the graph into two sets so that every edge
+    /// connects nodes from different sets. If that's not possible, the graph
+    /// contains a cycle of odd length and one is returned as an `OddCycle`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    /// use gdsl::ungraph::OddCycle;
+    ///
+    /// let mut g = ungraph![
+    ///     (char)
+    ///     ('A') => ['B', 'D']
+    ///     ('B') => ['C']
+    ///     ('C') => ['D']
+    ///     ('D') => []
+    /// ];
+    ///
+    /// let (a, b) = g.bipartition().unwrap();
+    ///
+    /// assert!(a.len() == 2 && b.len() == 2);
+    /// assert!(a.contains(&g['A']) == a.contains(&g['C']));
+    ///
+    /// g['A'].connect(&g['C'], ());
+    ///
+    /// match g.bipartition() {
+    ///     Err(OddCycle(cycle)) => assert!(cycle.len() == 4),
+    ///     _ => panic!("expected an odd cycle"),
+    /// }
+    /// ```
+    pub fn bipartition(
+        &self,
+    ) -> Result<(Vec<Node<K, N, E>>, Vec<Node<K, N, E>>), OddCycle<K, N, E>> {
+        for node in self.nodes.values() {
+            if let Some(edge) = node.iter().find(|Edge(u, v, _)| u == v) {
+                return Err(OddCycle(Path { edges: vec![edge] }));
+            }
+        }
+
+        let (nodes, adjacent) = self.index_adjacent();
+        let mut color = vec![None; nodes.len()];
+        let mut parent: Vec<Option<(usize, Edge<K, N, E>)>> = vec![None; nodes.len()];
+
+        for root in 0..nodes.len() {
+            if color[root].is_some() {
+                continue;
+            }
+            color[root] = Some(false);
+            let mut queue = VecDeque::from([root]);
+            while let Some(u) = queue.pop_front() {
+                for (v, _, edge) in &adjacent[u] {
+                    match color[*v] {
+                        None => {
+                            color[*v] = color[u].map(|c| !c);
+                            parent[*v] = Some((u, edge.clone()));
+                            queue.push_back(*v);
+                        }
+                        Some(c) if Some(c) == color[u] => {
+                            return Err(OddCycle(odd_cycle(&parent, u, *v, edge)));
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+
+        let (mut a, mut b) = (vec![], vec![]);
+        for (node, color) in nodes.into_iter().zip(color) {
+            match color {
+                Some(false) => a.push(node),
+                _ => b.push(node),
+            }
+        }
+        Ok((a, b))
+    }
+
+    fn sides(&self) -> Result<Sides<K, N, E>, OddCycle<K, N, E>> {
+        let (left, right) = self.bipartition()?;
+        let mut index = HashMap::default();
+        for (i, node) in left.iter().enumerate() {
+            index.insert(node.key().clone(), i);
+        }
+        for (j, node) in right.iter().enumerate() {
+            index.insert(node.key().clone(), j);
+        }
+
+        let mut edges = vec![];
+        let mut adjacent = vec![vec![]; left.len()];
+        for (l, node) in left.iter().enumerate() {
+            for edge in node.iter() {
+                if let Some(&r) = index.get(edge.1.key()) {
+                    adjacent[l].push(edges.len());
+                    edges.push((l, r, edge));
+                }
+            }
+        }
+        Ok(Sides {
+            left: left.len(),
+            right: right.len(),
+            edges,
+            adjacent,
+        })
+    }
+
+    /// Find a maximum matching of a bipartite graph using the Hopcroft-Karp
+    /// algorithm. Returns the matched edges, each pointing from the first
+    /// set of `bipartition` to the second. If the graph isn't bipartite,
+    /// `OddCycle` is returned. Runs in `O(E sqrt(V))` time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = ungraph![
+    ///     (&str)
+    ///     ("alice") => ["lathe", "mill"]
+    ///     ("bob") => ["lathe"]
+    ///     ("carol") => ["lathe"]
+    ///     ("lathe") => []
+    ///     ("mill") => []
+    /// ];
+    ///
+    /// let matching = g.maximum_matching().unwrap();
+    ///
+    /// assert!(matching.len() == 2);
+    /// ```
+    pub fn maximum_matching(&self) -> Result<Vec<Edge<K, N, E>>, OddCycle<K, N, E>> {
+        let sides = self.sides()?;
+        let mut mate_left: Vec<Option<usize>> = vec![None; sides.left];
+        let mut mate_right: Vec<Option<usize>> = vec![None; sides.right];
+
+        loop {
+            // Layer the free left nodes and the left nodes reachable from
+            // them through alternating paths.
+            let mut layer = vec![None; sides.left];
+            let mut queue = VecDeque::new();
+            for l in 0..sides.left {
+                if mate_left[l].is_none() {
+                    layer[l] = Some(0);
+                    queue.push_back(l);
+                }
+            }
+            let mut found = false;
+            while let Some(l) = queue.pop_front() {
+                for &e in &sides.adjacent[l] {
+                    match mate_right[sides.edges[e].1] {
+                        None => found = true,
+                        Some(m) => {
+                            let next = sides.edges[m].0;
+                            if layer[next].is_none() {
+                                layer[next] = layer[l].map(|d| d + 1);
+                                queue.push_back(next);
+                            }
+                        }
+                    }
+                }
+            }
+            if !found {
+                break;
+            }
+
+            let mut next = vec![0; sides.left];
+            for l in 0..sides.left {
+                if mate_left[l].is_none() {
+                    sides.augment(l, &layer, &mut next, &mut mate_left, &mut mate_right);
+                }
+            }
+        }
+
+        Ok(mate_left
+            .into_iter()
+            .flatten()
+            .map(|e| sides.edges[e].2.clone())
+            .collect())
+    }
+
+    /// Solve the assignment problem on a bipartite graph using the
+    /// Hungarian method. Finds a matching of maximum size whose total
+    /// weight is as small as possible, the weight of each edge is computed
+    /// from the edge value by `weight`. Negate the weights to maximize
+    /// instead. Returns the matched edges, each pointing from the first set
+    /// of `bipartition` to the second, together with their total weight. If
+    /// the graph isn't bipartite, `OddCycle` is returned. Runs in
+    /// `O(V^3)` time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = ungraph![
+    ///     (&str) => [i32]
+    ///     ("alice") => [ ("lathe", 4), ("mill", 1) ]
+    ///     ("bob") => [ ("lathe", 2), ("mill", 2) ]
+    ///     ("lathe") => []
+    ///     ("mill") => []
+    /// ];
+    ///
+    /// let (matching, total) = g.assignment(&|e| *e).unwrap();
+    ///
+    /// assert!(matching.len() == 2);
+    /// assert!(total == 3);
+    /// ```
+    pub fn assignment<W>(
+        &self,
+        weight: &dyn Fn(&E) -> W,
+    ) -> Result<(Vec<Edge<K, N, E>>, W), OddCycle<K, N, E>>
+    where
+        W: Copy + PartialOrd + Default + Add<Output = W> + Sub<Output = W>,
+    {
+        let sides = self.sides()?;
+        let (nl, nr) = (sides.left, sides.right);
+        let weights = sides
+            .edges
+            .iter()
+            .map(|(_, _, e)| weight(e.value()))
+            .collect::<Vec<_>>();
+        let mut mate_left: Vec<Option<usize>> = vec![None; nl];
+        let mut mate_right: Vec<Option<usize>> = vec![None; nr];
+
+        // Potentials of the left nodes followed by the right nodes. They keep
+        // the reduced cost of every residual arc non-negative so that the
+        // shortest augmenting path can be found with Dijkstra's algorithm.
+        let mut potential = vec![W::default(); nl + nr];
+        let mut reached = vec![false; nr];
+        for (e, &(_, r, _)) in sides.edges.iter().enumerate() {
+            if !reached[r] || weights[e] < potential[nl + r] {
+                potential[nl + r] = weights[e];
+                reached[r] = true;
+            }
+        }
+
+        loop {
+            let mut distance: Vec<Option<W>> = vec![None; nl + nr];
+            let mut via = vec![None; nl + nr];
+            let mut done = vec![false; nl + nr];
+            for l in 0..nl {
+                if mate_left[l].is_none() {
+                    distance[l] = Some(W::default() - potential[l]);
+                }
+            }
+
+            while let Some(u) = (0..nl + nr)
+                .filter(|&u| !done[u] && distance[u].is_some())
+                .reduce(|a, b| if distance[b] < distance[a] { b } else { a })
+            {
+                done[u] = true;
+                let d = distance[u].unwrap();
+                let mut relax = |v: usize, alt: W, e: usize| {
+                    if distance[v].is_none_or(|dv| alt < dv) {
+                        distance[v] = Some(alt);
+                        via[v] = Some(e);
+                    }
+                };
+                if u < nl {
+                    for &e in &sides.adjacent[u] {
+                        if mate_left[u] != Some(e) {
+                            let v = nl + sides.edges[e].1;
+                            relax(v, d + weights[e] + potential[u] - potential[v], e);
+                        }
+                    }
+                } else if let Some(e) = mate_right[u - nl] {
+                    let v = sides.edges[e].0;
+                    relax(v, d + potential[u] - potential[v] - weights[e], e);
+                }
+            }
+
+            // The cheapest augmenting path ends in the free right node with
+            // the smallest actual distance.
+            let Some(target) = (0..nr)
+                .filter(|&r| mate_right[r].is_none() && distance[nl + r].is_some())
+                .reduce(|a, b| {
+                    let real = |r: usize| distance[nl + r].unwrap() + potential[nl + r];
+                    if real(b) < real(a) {
+                        b
+                    } else {
+                        a
+                    }
+                })
+            else {
+                break;
+            };
+
+            let limit = distance[nl + target].unwrap();
+            for (p, d) in potential.iter_mut().zip(&distance) {
+                *p = match d {
+                    Some(d) if *d < limit => *p + *d,
+                    _ => *p + limit,
+                };
+            }
+
+            let mut r = target;
+            loop {
+                let e = via[nl + r].unwrap();
+                let l = sides.edges[e].0;
+                mate_right[r] = Some(e);
+                match mate_left[l].replace(e) {
+                    Some(previous) => r = sides.edges[previous].1,
+                    None => break,
+                }
+            }
+        }
+
+        let mut total = W::default();
+        let mut matching = vec![];
+        for e in mate_left.into_iter().flatten() {
+            total = total + weights[e];
+            matching.push(sides.edges[e].2.clone());
+        }
+        Ok((matching, total))
+    }
+}
+
+impl<K, N, E> Sides<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    /// Finds an augmenting path from the free left node `l` along the layers
+    /// and flips the matching along it. Edges that lead to dead ends are
+    /// skipped for the rest of the phase. The path is searched depth-first
+    /// on an explicit stack of left nodes, each paired with the edge it
+    /// currently tries through `next`, since it can span the whole graph.
+    fn augment(
+        &self,
+        l: usize,
+        layer: &[Option<usize>],
+        next: &mut [usize],
+        mate_left: &mut [Option<usize>],
+        mate_right: &mut [Option<usize>],
+    ) -> bool {
+        let mut stack = vec![l];
+        while let Some(&u) = stack.last() {
+            if next[u] == self.adjacent[u].len() {
+                stack.pop();
+                if let Some(&parent) = stack.last() {
+                    next[parent] += 1;
+                }
+                continue;
+            }
+            let e = self.adjacent[u][next[u]];
+            match mate_right[self.edges[e].1] {
+                None => {
+                    for &u in &stack {
+                        let e = self.adjacent[u][next[u]];
+                        next[u] += 1;
+                        mate_left[u] = Some(e);
+                        mate_right[self.edges[e].1] = Some(e);
+                    }
+                    return true;
+                }
+                Some(m) => {
+                    let other = self.edges[m].0;
+                    if layer[other] == layer[u].map(|d| d + 1) {
+                        stack.push(other);
+                    } else {
+                        next[u] += 1;
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+/// Builds the odd cycle closed by the edge between `u` and `v`, two nodes of
+/// the same color in the breadth-first tree described by `parent`.
+fn odd_cycle<K, N, E>(
+    parent: &[Option<(usize, Edge<K, N, E>)>],
+    mut u: usize,
+    mut v: usize,
+    edge: &Edge<K, N, E>,
+) -> Path<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    // Both nodes are on the same depth of the tree, climb until they meet.
+    let (mut down, mut up) = (vec![], vec![]);
+    while u != v {
+        let (pu, eu) = parent[u].clone().unwrap();
+        let (pv, ev) = parent[v].clone().unwrap();
+        down.push(eu);
+        up.push(ev.reverse());
+        u = pu;
+        v = pv;
+    }
+    down.reverse();
+    down.push(edge.clone());
+    down.extend(up);
+    Path { edges: down }
+}
@@ -5,7 +5,9 @@ use serde::{
     Deserialize,
 };
 
-fn graph_serde_decompose<K, N, E>(g: &Graph<K, N, E>) -> (Vec<(K, N)>, Vec<(K, K, E)>)
+type GraphDecomp<K, N, E> = (Vec<(K, N)>, Vec<(K, K, E)>, Vec<(usize, usize)>);
+
+fn graph_serde_decompose<K, N, E>(g: &Graph<K, N, E>) -> GraphDecomp<K, N, E>
 where
     K: Clone + Hash + PartialEq + Eq + Display + Serialize,
     N: Clone + Serialize,
@@ -13,16 +15,22 @@ where
 {
     let mut nodes = Vec::new();
     let mut edges = Vec::new();
+    let mut keys = Vec::new();
 
     for (_, n) in g.iter() {
         nodes.push((n.key().clone(), n.value().clone()));
 
-        for Edge(u, v, e) in n.iter() {
-            edges.push((u.key().clone(), v.key().clone(), e));
+        for (v, key, e) in n.outbound_keyed() {
+            // Edges made with `connect` have the key `0` and are written
+            // without one, only the other keys are listed by edge index.
+            if key != 0 {
+                keys.push((edges.len(), key));
+            }
+            edges.push((n.key().clone(), v.key().clone(), e));
         }
     }
 
-    (nodes, edges)
+    (nodes, edges, keys)
 }
 
 impl<K, N, E> Serialize for Graph<K, N, E>
@@ -35,10 +43,11 @@ where
     where
         S: Serializer,
     {
-        let mut tuple = serializer.serialize_tuple(2)?;
-        let (nodes, edges) = graph_serde_decompose(self);
+        let mut tuple = serializer.serialize_tuple(3)?;
+        let (nodes, edges, keys) = graph_serde_decompose(self);
         tuple.serialize_element(&nodes)?;
         tuple.serialize_element(&edges)?;
+        tuple.serialize_element(&keys)?;
         tuple.end()
     }
 }
@@ -71,7 +80,7 @@ where
             type Value = Graph<K, N, E>;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                formatter.write_str("node and edge lists")
+                formatter.write_str("node, edge and edge key lists")
             }
 
             fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
@@ -80,6 +89,7 @@ where
             {
                 let mut nodes = Vec::new();
                 let mut edges: Vec<(K, K, E)> = Vec::new();
+                let mut keys: Vec<(usize, usize)> = Vec::new();
 
                 if let Some(node_seq) = seq.next_element()? {
                     nodes = node_seq;
@@ -89,13 +99,34 @@ where
                     edges = edge_seq;
                 }
 
+                // Data written before edges had keys has no key list.
+                if let Some(key_seq) = seq.next_element()? {
+                    keys = key_seq;
+                }
+
+                let mut edge_keys = vec![0; edges.len()];
+                for (i, key) in keys {
+                    if key == 0 {
+                        return Err(de::Error::custom("The edge key 0 is reserved!"));
+                    }
+                    match edge_keys.get_mut(i) {
+                        Some(edge_key) => *edge_key = key,
+                        None => {
+                            return Err(de::Error::custom(format!(
+                                "Can't set the key {} of the edge {} because it doesn't exist!",
+                                key, i
+                            )))
+                        }
+                    }
+                }
+
                 let mut g = Graph::new();
 
                 for (k, v) in nodes {
                     g.insert(Node::new(k, v));
                 }
 
-                for (u, v, e) in edges {
+                for ((u, v, e), key) in edges.into_iter().zip(edge_keys) {
                     let un = g.get(&u).ok_or_else(|| {
                         de::Error::custom(format!(
                             "Can't connect {} => {} because {} doesn't exist!",
@@ -108,15 +139,27 @@ where
                             u, v, v
                         ))
                     })?;
-                    Node::connect(&un, &vn, e);
+                    if key == 0 {
+                        Node::connect(&un, &vn, e);
+                    } else {
+                        Node::connect_keyed(&un, &vn, key, e).map_err(|_| {
+                            de::Error::custom(format!(
+                                "Can't connect {} => {} because the key {} is taken!",
+                                u, v, key
+                            ))
+                        })?;
+                    }
                 }
 
                 Ok(g)
             }
         }
 
-        deserializer.deserialize_seq(GraphVisitor {
-            _phantom: std::marker::PhantomData,
-        })
+        deserializer.deserialize_tuple(
+            3,
+            GraphVisitor {
+                _phantom: std::marker::PhantomData,
+            },
+        )
     }
 }
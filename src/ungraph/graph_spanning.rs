@@ -0,0 +1,188 @@
+use super::*;
+use crate::visit::Visit;
+use ahash::AHashSet as HashSet;
+use std::{cmp::Ordering, collections::BinaryHeap, ops::Add};
+
+/// Disjoint set of node indices with path halving and union by size.
+struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(len: usize) -> Self {
+        UnionFind {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+        }
+    }
+
+    fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merge the sets of `a` and `b`. Returns false if they were already in
+    /// the same set.
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        true
+    }
+}
+
+impl<K, N, E> Graph<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    /// Find a minimum spanning forest of the graph, a minimum spanning tree
+    /// for each connected component. The weight of each edge is computed
+    /// from the edge value by `weight`. Returns the edges of the forest
+    /// together with their total weight. Uses Kruskal's algorithm.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = ungraph![
+    ///     (char) => [u64]
+    ///     ('A') => [ ('B', 1), ('C', 4) ]
+    ///     ('B') => [ ('C', 2), ('D', 7) ]
+    ///     ('C') => [ ('D', 3) ]
+    ///     ('D') => []
+    ///     ('E') => [ ('F', 5) ]
+    ///     ('F') => []
+    /// ];
+    ///
+    /// let (edges, total) = g.minimum_spanning_forest(&|e| *e);
+    ///
+    /// assert!(edges.len() == 4);
+    /// assert!(total == 11);
+    /// ```
+    pub fn minimum_spanning_forest<W>(&self, weight: &dyn Fn(&E) -> W) -> (Vec<Edge<K, N, E>>, W)
+    where
+        W: Copy + PartialOrd + Default + Add<Output = W>,
+    {
+        self.kruskal(weight)
+    }
+
+    /// Find a minimum spanning forest of the graph using Kruskal's
+    /// algorithm. The edges are sorted by weight and added to the forest
+    /// unless they would close a cycle. Runs in `O(E log E)` time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = ungraph![
+    ///     (char) => [f64]
+    ///     ('A') => [ ('B', 0.5), ('C', 0.1) ]
+    ///     ('B') => [ ('C', 0.2) ]
+    ///     ('C') => []
+    /// ];
+    ///
+    /// let (edges, total) = g.kruskal(&|e| *e);
+    ///
+    /// assert!(edges.len() == 2);
+    /// assert!((total - 0.3).abs() < 1e-9);
+    /// ```
+    pub fn kruskal<W>(&self, weight: &dyn Fn(&E) -> W) -> (Vec<Edge<K, N, E>>, W)
+    where
+        W: Copy + PartialOrd + Default + Add<Output = W>,
+    {
+        let (nodes, adjacent) = self.index_adjacent();
+        let mut seen = HashSet::default();
+        let mut edges = adjacent
+            .into_iter()
+            .enumerate()
+            .flat_map(|(u, list)| list.into_iter().map(move |(v, id, edge)| (u, v, id, edge)))
+            .filter(|(_, _, id, _)| seen.insert(*id))
+            .map(|(u, v, _, edge)| (weight(edge.value()), u, v, edge))
+            .collect::<Vec<_>>();
+        edges.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+        let mut sets = UnionFind::new(nodes.len());
+        let mut forest = vec![];
+        let mut total = W::default();
+        for (w, u, v, edge) in edges {
+            if sets.union(u, v) {
+                total = total + w;
+                forest.push(edge);
+            }
+        }
+        (forest, total)
+    }
+
+    /// Find a minimum spanning forest of the graph using Prim's algorithm.
+    /// Each tree is grown from an arbitrary node by repeatedly adding the
+    /// lightest edge leaving the tree. Runs in `O(E log V)` time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = ungraph![
+    ///     (char) => [u64]
+    ///     ('A') => [ ('B', 1), ('C', 4) ]
+    ///     ('B') => [ ('C', 2), ('D', 7) ]
+    ///     ('C') => [ ('D', 3) ]
+    ///     ('D') => []
+    /// ];
+    ///
+    /// let (edges, total) = g.prim(&|e| *e);
+    ///
+    /// assert!(edges.len() == 3);
+    /// assert!(total == 6);
+    /// ```
+    pub fn prim<W>(&self, weight: &dyn Fn(&E) -> W) -> (Vec<Edge<K, N, E>>, W)
+    where
+        W: Copy + PartialOrd + Default + Add<Output = W>,
+    {
+        let (nodes, adjacent) = self.index_adjacent();
+        let mut in_tree = vec![false; nodes.len()];
+        let mut forest = vec![];
+        let mut total = W::default();
+
+        for root in 0..nodes.len() {
+            if in_tree[root] {
+                continue;
+            }
+            in_tree[root] = true;
+            let mut queue = BinaryHeap::new();
+            for (i, (_, _, edge)) in adjacent[root].iter().enumerate() {
+                queue.push(Visit(weight(edge.value()), (root, i)));
+            }
+
+            while let Some(Visit(w, (u, i))) = queue.pop() {
+                let (v, _, edge) = &adjacent[u][i];
+                if in_tree[*v] {
+                    continue;
+                }
+                in_tree[*v] = true;
+                total = total + w;
+                forest.push(edge.clone());
+                for (j, (x, _, edge)) in adjacent[*v].iter().enumerate() {
+                    if !in_tree[*x] {
+                        queue.push(Visit(weight(edge.value()), (*v, j)));
+                    }
+                }
+            }
+        }
+        (forest, total)
+    }
+}
@@ -1,7 +1,12 @@
 //! Undirected Graph
 
+mod graph_centrality;
+mod graph_components;
+mod graph_isomorphism;
 mod graph_macros;
+mod graph_matching;
 mod graph_serde;
+mod graph_spanning;
 mod node;
 
 use std::{
@@ -11,6 +16,11 @@ use std::{
 
 use ahash::HashMap;
 
+use crate::traits::GraphBase;
+
+pub use self::graph_centrality::PageRank;
+pub use self::graph_isomorphism::{Isomorphism, Mappings};
+pub use self::graph_matching::OddCycle;
 pub use self::node::*;
 
 pub struct Graph<K, N, E>
@@ -139,7 +149,9 @@ where
         }
     }
 
-    /// Remove a node from the Graph
+    /// Remove a node from the Graph. The node is isolated, so all of its
+    /// inbound and outbound connections are removed from the nodes that
+    /// remain in the graph.
     ///
     /// # Examples
     ///
@@ -153,12 +165,18 @@ where
     ///
     /// assert!(g.contains(&"A"));
     ///
-    /// g.remove(&"A");
+    /// g["A"].connect(&g["B"], 0);
+    ///
+    /// let a = g.remove(&"A").unwrap();
     ///
     /// assert!(g.contains(&"A") == false);
+    /// assert!(a.is_orphan());
+    /// assert!(g[&"B"].is_orphan());
     /// ```
     pub fn remove(&mut self, node: &K) -> Option<Node<K, N, E>> {
-        self.nodes.remove(node)
+        let node = self.nodes.remove(node)?;
+        node.isolate();
+        Some(node)
     }
 
     /// Collect nodes into a vector
@@ -231,6 +249,43 @@ where
         self.nodes.iter()
     }
 
+    /// Compute the shortest paths from the node with the given key to every
+    /// reachable node using Dijkstra's algorithm. The weight of each edge is
+    /// computed from the edge value by `weight`. Returns `None` if there is
+    /// no node with the given key in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdsl::*;
+    ///
+    /// let g = ungraph![
+    ///     (char) => [u64]
+    ///     ('A') => [ ('B', 4), ('C', 1) ]
+    ///     ('B') => [ ('D', 1) ]
+    ///     ('C') => [ ('B', 2), ('D', 5) ]
+    ///     ('D') => []
+    /// ];
+    ///
+    /// let paths = g.shortest_paths(&'A', &|e| *e).unwrap();
+    ///
+    /// assert!(paths.distance(&'D') == Some(4));
+    ///
+    /// let nodes = paths.path_to(&'D').unwrap().to_vec_nodes();
+    ///
+    /// assert!(nodes == vec![g['A'].clone(), g['C'].clone(), g['B'].clone(), g['D'].clone()]);
+    /// ```
+    pub fn shortest_paths<W>(
+        &self,
+        source: &K,
+        weight: &dyn Fn(&E) -> W,
+    ) -> Option<ShortestPaths<K, N, E, W>>
+    where
+        W: Copy + PartialOrd + Default + std::ops::Add<Output = W>,
+    {
+        self.get(source).map(|node| node.dijkstra(weight).search())
+    }
+
     pub fn to_dot(&self) -> String {
         let mut s = String::new();
         s.push_str("digraph {\n");
@@ -245,53 +300,38 @@ where
         s
     }
 
-    fn fmt_attr(attrs: Vec<(String, String)>) -> String {
-        let mut s = String::new();
-        for (k, v) in attrs {
-            write!(&mut s, "[{}=\"{}\"]", k, v).unwrap();
-        }
-        s
-    }
-
     pub fn to_dot_with_attr(
         &self,
         gattr: &dyn Fn(&Self) -> Option<Vec<(String, String)>>,
         nattr: &dyn Fn(&Node<K, N, E>) -> Option<Vec<(String, String)>>,
         eattr: &dyn Fn(&Node<K, N, E>, &Node<K, N, E>, &E) -> Option<Vec<(String, String)>>,
     ) -> String {
-        let mut s = String::new();
-        s.push_str("digraph {\n");
-        if let Some(gattrs) = gattr(self) {
-            for (k, v) in gattrs {
-                s.push_str(&format!("\t{}=\"{}\"\n", k, v));
-            }
-        }
-        for (u_key, node) in self.iter() {
-            s.push_str(&format!("\t{}", u_key.clone()));
-            if let Some(nattr) = nattr(node) {
-                s.push_str(&format!(" {}", Self::fmt_attr(nattr)));
-            }
-            s.push('\n');
-        }
-        for (_, node) in self.iter() {
-            for Edge(u, v, e) in node {
-                s.push_str(&format!("\t{} -> {}", u.key(), v.key()));
-                if let Some(eattrs) = eattr(&u, &v, &e) {
-                    s.push_str(&format!(" {}", Self::fmt_attr(eattrs)));
-                }
-                s.push('\n');
-            }
-        }
-        s.push('}');
-        s
+        GraphBase::to_dot_with_attr(self, gattr, nattr, eattr)
     }
 
     pub fn sizeof(&self) -> usize {
-        let mut size = 0;
-        for (k, node) in self.iter() {
-            size += node.sizeof() + std::mem::size_of_val(k);
-        }
-        size
+        GraphBase::sizeof(self)
+    }
+}
+
+impl<K, N, E> GraphBase for Graph<K, N, E>
+where
+    K: Clone + Hash + Display + PartialEq + Eq,
+    N: Clone,
+    E: Clone,
+{
+    type Node = Node<K, N, E>;
+
+    fn node(&self, key: &K) -> Option<Node<K, N, E>> {
+        self.get(key)
+    }
+
+    fn node_count(&self) -> usize {
+        self.len()
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = Node<K, N, E>> + '_ {
+        self.nodes.values().cloned()
     }
 }
 
@@ -1,6 +1,21 @@
 use super::*;
 use crate::error::Error;
 
+/// The value of an edge. It is stored once and shared by the adjacency lists
+/// of both of its endpoints.
+pub type EdgeValue<E> = Rc<RefCell<E>>;
+
+/// Take the value out of an edge that was removed from the adjacency lists.
+/// The value is cloned if it is still shared.
+pub fn take_value<E: Clone>(value: EdgeValue<E>) -> E {
+    match Rc::try_unwrap(value) {
+        Ok(value) => value.into_inner(),
+        Err(value) => value.borrow().clone(),
+    }
+}
+
+type InnerEdge<K, N, E> = (WeakNode<K, N, E>, EdgeValue<E>, usize);
+
 #[derive(Clone)]
 pub struct WeakNode<K = usize, N = (), E = ()>
 where
@@ -26,6 +41,13 @@ where
             inner: Rc::downgrade(&node.inner),
         }
     }
+
+    /// Returns true if the node has been dropped. Edges to dropped nodes are
+    /// skipped when the adjacency lists are read and pruned from a list the
+    /// next time an edge is removed from it.
+    pub fn is_dangling(&self) -> bool {
+        self.inner.strong_count() == 0
+    }
 }
 
 pub struct Adjacent<K, N, E>
@@ -34,8 +56,8 @@ where
     N: Clone,
     E: Clone,
 {
-    outbound: Vec<(WeakNode<K, N, E>, E)>,
-    inbound: Vec<(WeakNode<K, N, E>, E)>,
+    outbound: Vec<InnerEdge<K, N, E>>,
+    inbound: Vec<InnerEdge<K, N, E>>,
 }
 
 impl<K, N, E> Adjacent<K, N, E>
@@ -51,7 +73,7 @@ where
         })
     }
 
-    pub fn get_adjacent(&self, idx: usize) -> Option<(&WeakNode<K, N, E>, &E)> {
+    pub fn get_adjacent(&self, idx: usize) -> Option<(&WeakNode<K, N, E>, &EdgeValue<E>)> {
         match self.outbound.get(idx) {
             Some(edge) => Some((&edge.0, &edge.1)),
             None => self
@@ -61,25 +83,25 @@ where
         }
     }
 
-    pub fn find_outbound(&self, node: &K) -> Option<(&WeakNode<K, N, E>, &E)> {
+    pub fn find_outbound(&self, node: &K) -> Option<(&WeakNode<K, N, E>, &EdgeValue<E>)> {
         for edge in self.outbound.iter() {
-            if edge.0.upgrade().unwrap().key() == node {
+            if edge.0.upgrade().is_some_and(|n| n.key() == node) {
                 return Some((&edge.0, &edge.1));
             }
         }
         None
     }
 
-    pub fn find_inbound(&self, node: &K) -> Option<(&WeakNode<K, N, E>, &E)> {
+    pub fn find_inbound(&self, node: &K) -> Option<(&WeakNode<K, N, E>, &EdgeValue<E>)> {
         for edge in self.inbound.iter() {
-            if edge.0.upgrade().unwrap().key() == node {
+            if edge.0.upgrade().is_some_and(|n| n.key() == node) {
                 return Some((&edge.0, &edge.1));
             }
         }
         None
     }
 
-    pub fn find_adjacent(&self, node: &K) -> Option<(&WeakNode<K, N, E>, &E)> {
+    pub fn find_adjacent(&self, node: &K) -> Option<(&WeakNode<K, N, E>, &EdgeValue<E>)> {
         match self.find_outbound(node) {
             Some(edge) => Some(edge),
             None => self.find_inbound(node),
@@ -87,46 +109,98 @@ where
     }
 
     pub fn len_outbound(&self) -> usize {
-        self.outbound.len()
+        self.outbound
+            .iter()
+            .filter(|edge| !edge.0.is_dangling())
+            .count()
     }
 
     pub fn len_inbound(&self) -> usize {
-        self.inbound.len()
+        self.inbound
+            .iter()
+            .filter(|edge| !edge.0.is_dangling())
+            .count()
     }
 
-    pub fn push_inbound(&mut self, edge: (Node<K, N, E>, E)) {
-        self.inbound.push((WeakNode::downgrade(&edge.0), edge.1));
+    pub fn push_inbound(&mut self, edge: (Node<K, N, E>, EdgeValue<E>, usize)) {
+        self.inbound
+            .push((WeakNode::downgrade(&edge.0), edge.1, edge.2));
     }
 
-    pub fn push_outbound(&mut self, edge: (Node<K, N, E>, E)) {
-        self.outbound.push((WeakNode::downgrade(&edge.0), edge.1));
+    pub fn push_outbound(&mut self, edge: (Node<K, N, E>, EdgeValue<E>, usize)) {
+        self.outbound
+            .push((WeakNode::downgrade(&edge.0), edge.1, edge.2));
     }
 
-    pub fn remove_inbound(&mut self, source: &K) -> Result<E, Error> {
+    pub fn remove_inbound(&mut self, source: &K) -> Result<EdgeValue<E>, Error> {
+        self.inbound.retain(|edge| !edge.0.is_dangling());
         for (idx, edge) in self.inbound.iter().enumerate() {
-            if edge.0.upgrade().unwrap().key() == source {
+            if edge.0.upgrade().is_some_and(|n| n.key() == source) {
                 return Ok(self.inbound.remove(idx).1);
             }
         }
         Err(Error::EdgeNotFound)
     }
 
-    pub fn remove_outbound(&mut self, target: &K) -> Result<E, Error> {
+    pub fn remove_outbound(&mut self, target: &K) -> Result<EdgeValue<E>, Error> {
+        self.outbound.retain(|edge| !edge.0.is_dangling());
         for (idx, edge) in self.outbound.iter().enumerate() {
-            if edge.0.upgrade().unwrap().key() == target {
+            if edge.0.upgrade().is_some_and(|n| n.key() == target) {
                 return Ok(self.outbound.remove(idx).1);
             }
         }
         Err(Error::EdgeNotFound)
     }
 
-    pub fn remove_undirected(&mut self, node: &K) -> Result<E, Error> {
+    pub fn remove_undirected(&mut self, node: &K) -> Result<EdgeValue<E>, Error> {
         match self.remove_inbound(node) {
             Ok(edge) => Ok(edge),
             Err(_) => self.remove_outbound(node),
         }
     }
 
+    /// Removes the edge that shares the given value, the other half of an
+    /// undirected edge removed from the adjacency lists of its other endpoint.
+    pub fn remove_shared(&mut self, value: &EdgeValue<E>) -> Result<EdgeValue<E>, Error> {
+        for edges in [&mut self.outbound, &mut self.inbound] {
+            edges.retain(|edge| !edge.0.is_dangling());
+            if let Some(idx) = edges.iter().position(|edge| Rc::ptr_eq(&edge.1, value)) {
+                return Ok(edges.remove(idx).1);
+            }
+        }
+        Err(Error::EdgeNotFound)
+    }
+
+    pub fn parallel_outbound<'a>(
+        &'a self,
+        node: &'a K,
+    ) -> impl Iterator<Item = (usize, &'a EdgeValue<E>)> + 'a {
+        parallel(&self.outbound, node)
+    }
+
+    pub fn parallel_inbound<'a>(
+        &'a self,
+        node: &'a K,
+    ) -> impl Iterator<Item = (usize, &'a EdgeValue<E>)> + 'a {
+        parallel(&self.inbound, node)
+    }
+
+    pub fn remove_inbound_keyed(&mut self, source: &K, key: usize) -> Result<EdgeValue<E>, Error> {
+        remove_keyed(&mut self.inbound, source, key)
+    }
+
+    pub fn remove_outbound_keyed(&mut self, target: &K, key: usize) -> Result<EdgeValue<E>, Error> {
+        remove_keyed(&mut self.outbound, target, key)
+    }
+
+    pub fn keyed_outbound(
+        &self,
+    ) -> impl Iterator<Item = (Node<K, N, E>, usize, &EdgeValue<E>)> + '_ {
+        self.outbound
+            .iter()
+            .filter_map(|edge| edge.0.upgrade().map(|node| (node, edge.2, &edge.1)))
+    }
+
     pub fn clear_inbound(&mut self) {
         self.inbound.clear();
     }
@@ -135,10 +209,51 @@ where
         self.outbound.clear();
     }
 
+    /// Returns the size of the adjacency lists in bytes. The value of an edge
+    /// is stored once for both of its endpoints, so it is counted with the
+    /// outbound edge only, together with its reference counts.
     pub fn sizeof(&self) -> usize {
-        self.inbound.len()
-            + self.outbound.len()
-                * (std::mem::size_of::<Node<K, N, E>>() + std::mem::size_of::<E>())
+        let edge = std::mem::size_of::<InnerEdge<K, N, E>>();
+        let value = 2 * std::mem::size_of::<usize>() + std::mem::size_of::<RefCell<E>>();
+        (self.inbound.len() + self.outbound.len()) * edge
+            + self.outbound.len() * value
             + std::mem::size_of::<Self>()
     }
 }
+
+/// Returns the keys and values of the edges in the list to the node with the
+/// given key in the order they were created.
+fn parallel<'a, K, N, E>(
+    edges: &'a [InnerEdge<K, N, E>],
+    node: &'a K,
+) -> impl Iterator<Item = (usize, &'a EdgeValue<E>)> + 'a
+where
+    K: Clone + Hash + PartialEq + Eq + Display,
+    N: Clone,
+    E: Clone,
+{
+    edges
+        .iter()
+        .filter(move |edge| edge.0.upgrade().is_some_and(|n| n.key() == node))
+        .map(|edge| (edge.2, &edge.1))
+}
+
+fn remove_keyed<K, N, E>(
+    edges: &mut Vec<InnerEdge<K, N, E>>,
+    node: &K,
+    key: usize,
+) -> Result<EdgeValue<E>, Error>
+where
+    K: Clone + Hash + PartialEq + Eq + Display,
+    N: Clone,
+    E: Clone,
+{
+    edges.retain(|edge| !edge.0.is_dangling());
+    match edges
+        .iter()
+        .position(|edge| edge.2 == key && edge.0.upgrade().is_some_and(|n| n.key() == node))
+    {
+        Some(idx) => Ok(edges.remove(idx).1),
+        None => Err(Error::EdgeNotFound),
+    }
+}
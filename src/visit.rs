@@ -0,0 +1,31 @@
+//! Priority queue entry shared by the shortest path and spanning tree
+//! algorithms of all graph modules.
+
+use std::cmp::Ordering;
+
+/// Entry in a `BinaryHeap` holding a distance and the item reached at that
+/// distance. Entries are ordered by reversed distance so that the heap pops
+/// the closest item first. Distances are only required to be `PartialOrd` so
+/// that floating point weights can be used, incomparable distances are
+/// treated as equal.
+pub(crate) struct Visit<W, T>(pub W, pub T);
+
+impl<W: PartialOrd, T> PartialEq for Visit<W, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: PartialOrd, T> Eq for Visit<W, T> {}
+
+impl<W: PartialOrd, T> PartialOrd for Visit<W, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: PartialOrd, T> Ord for Visit<W, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.partial_cmp(&self.0).unwrap_or(Ordering::Equal)
+    }
+}
@@ -3,7 +3,7 @@ fn ut_digraph_manual_bfs() {
     use gdsl::digraph::*;
     use std::collections::{HashSet, VecDeque};
 
-    let g = vec![
+    let g = [
         Node::new(0, ()),
         Node::new(1, ()),
         Node::new(2, ()),
@@ -24,7 +24,7 @@ fn ut_digraph_manual_bfs() {
     let mut queue = VecDeque::new();
 
     queue.push_back(g[0].clone());
-    visited.insert(g[0].key().clone());
+    visited.insert(*g[0].key());
 
     while let Some(node) = queue.pop_front() {
         for Edge(_, v, _) in &node {
@@ -32,7 +32,7 @@ fn ut_digraph_manual_bfs() {
                 if v == g[4] {
                     return;
                 }
-                visited.insert(v.key().clone());
+                visited.insert(*v.key());
                 queue.push_back(v);
             }
         }
@@ -306,6 +306,22 @@ fn ut_digraph_sizes() {
     assert!(n4.sizeof() == 80);
 }
 
+#[test]
+fn ut_digraph_sizes_shared_edges() {
+    use gdsl::digraph::*;
+
+    let n1 = Node::<usize, (), u64>::new(1, ());
+    let n2 = Node::<usize, (), u64>::new(2, ());
+    let (s1, s2) = (n1.sizeof(), n2.sizeof());
+
+    n1.connect(&n2, 7);
+
+    // Both ends hold a 24 byte list entry and the source also counts the
+    // 32 byte shared value with its reference counts.
+    assert!(n1.sizeof() == s1 + 56);
+    assert!(n2.sizeof() == s2 + 24);
+}
+
 #[test]
 fn ut_digraph_deref_node() {
     use gdsl::digraph::*;
@@ -326,6 +342,7 @@ fn ut_digraph_deref_node() {
 }
 
 #[test]
+#[allow(clippy::unit_cmp)]
 fn ut_serde_json() {
     use gdsl::digraph::*;
     use gdsl::*;
@@ -360,6 +377,7 @@ fn ut_serde_json() {
 }
 
 #[test]
+#[allow(clippy::unit_cmp)]
 fn ut_serde_cbor() {
     use gdsl::digraph::*;
     use gdsl::*;
@@ -521,7 +539,7 @@ fn ut_digraph_scc() {
     // Since the graph container is a hash map, the order of the SCCs is not
     // deterministic. We sort the SCCs by their size to make the test
     // deterministic.
-    scc.sort_by(|a, b| a.len().cmp(&b.len()));
+    scc.sort_by_key(|a| a.len());
 
     assert!(scc.len() == 4);
     assert!(scc[0].len() == 1);
@@ -570,6 +588,1890 @@ fn ut_digraph_dijkstra() {
     assert!(g['I'].get() == 15);
 }
 
+#[test]
+fn ut_digraph_shortest_paths() {
+    use gdsl::*;
+
+    let g = digraph![
+        (char) => [u64]
+        ('A') => [ ('B', 4), ('H', 8) ]
+        ('B') => [ ('A', 4), ('H', 11), ('C', 8) ]
+        ('C') => [ ('B', 8), ('C', 2), ('F', 4), ('D', 7) ]
+        ('D') => [ ('C', 7), ('F', 14), ('E', 9) ]
+        ('E') => [ ('D', 9), ('F', 10) ]
+        ('F') => [ ('G', 2), ('C', 4), ('D', 14), ('E', 10) ]
+        ('G') => [ ('H', 1), ('I', 6), ('F', 2) ]
+        ('H') => [ ('A', 8), ('B', 11), ('I', 7), ('G', 1) ]
+        ('I') => [ ('H', 7), ('C', 2), ('G', 6) ]
+    ];
+
+    let paths = g.shortest_paths(&'A', &|e| *e).unwrap();
+
+    assert!(paths.distance(&'A') == Some(0));
+    assert!(paths.distance(&'B') == Some(4));
+    assert!(paths.distance(&'C') == Some(12));
+    assert!(paths.distance(&'D') == Some(19));
+    assert!(paths.distance(&'E') == Some(21));
+    assert!(paths.distance(&'F') == Some(11));
+    assert!(paths.distance(&'G') == Some(9));
+    assert!(paths.distance(&'H') == Some(8));
+    assert!(paths.distance(&'I') == Some(15));
+
+    let distances: &std::collections::HashMap<char, u64> = paths.distances();
+    assert!(distances.len() == 9);
+
+    let path = paths.path_to(&'E').unwrap().to_vec_nodes();
+
+    assert!(
+        path == vec![
+            g['A'].clone(),
+            g['H'].clone(),
+            g['G'].clone(),
+            g['F'].clone(),
+            g['E'].clone()
+        ]
+    );
+    assert!(paths.path_to(&'A').is_none());
+
+    let paths = g['E'].dijkstra(&|e| *e as f64 * 0.5).transpose().search();
+
+    assert!(paths.distance(&'A') == Some(10.5));
+    assert!(paths.predecessor(&'A').unwrap().source() == &g['H']);
+}
+
+#[test]
+fn ut_digraph_astar() {
+    use gdsl::digraph::*;
+
+    // A 4x4 grid where each node is keyed by its (x, y) coordinate encoded
+    // as `y * 4 + x` and connected to its right and lower neighbours.
+    let grid = (0..16).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+
+    for i in 0..16 {
+        if i % 4 != 3 {
+            grid[i].connect(&grid[i + 1], 1);
+        }
+        if i < 12 {
+            grid[i].connect(&grid[i + 4], 1);
+        }
+    }
+
+    let manhattan = |node: &Node<i32, (), i32>| (3 - node.key() % 4) + (3 - node.key() / 4);
+
+    let (path, cost) = grid[0]
+        .astar(&|Edge(_, _, e)| *e, &manhattan)
+        .target(&15)
+        .search_path()
+        .unwrap();
+
+    assert!(cost == 6);
+    assert!(path.to_vec_nodes().len() == 7);
+
+    // Walls block the whole second column except for the bottom row.
+    let walls = [1, 5, 9];
+    let (path, cost) = grid[0]
+        .astar(&|Edge(_, _, e)| *e, &manhattan)
+        .filter(&mut |Edge(_, v, _)| !walls.contains(v.key()))
+        .target(&15)
+        .search_path()
+        .unwrap();
+
+    assert!(cost == 6);
+    assert!(path[0] == Edge(grid[0].clone(), grid[4].clone(), 1));
+    assert!(path[1] == Edge(grid[4].clone(), grid[8].clone(), 1));
+    assert!(path[2] == Edge(grid[8].clone(), grid[12].clone(), 1));
+
+    let (path, cost) = grid[15]
+        .astar(&|Edge(_, _, e)| *e, &|node| {
+            *node.key() % 4 + *node.key() / 4
+        })
+        .transpose()
+        .target(&0)
+        .search_path()
+        .unwrap();
+
+    assert!(cost == 6);
+    assert!(path.last_edge().unwrap().target() == &grid[0]);
+    assert!(grid[0]
+        .astar(&|_| 1, &|_| 0)
+        .target(&16)
+        .search_path()
+        .is_none());
+}
+
+#[test]
+fn ut_digraph_bellman_ford() {
+    use gdsl::digraph::{BellmanFordError, NegativeCycle};
+    use gdsl::*;
+
+    let g = digraph![
+        (usize) => [i64]
+        (0) => [ (1, 6), (2, 7) ]
+        (1) => [ (2, 8), (3, 5), (4, -4) ]
+        (2) => [ (3, -3), (4, 9) ]
+        (3) => [ (1, -2) ]
+        (4) => [ (0, 2), (3, 7) ]
+    ];
+
+    let paths = g.bellman_ford(&0, &|e| *e).unwrap();
+
+    assert!(paths.distance(&0) == Some(0));
+    assert!(paths.distance(&1) == Some(2));
+    assert!(paths.distance(&2) == Some(7));
+    assert!(paths.distance(&3) == Some(4));
+    assert!(paths.distance(&4) == Some(-2));
+
+    let path = paths.path_to(&4).unwrap().to_vec_nodes();
+
+    assert!(
+        path == vec![
+            g[0].clone(),
+            g[2].clone(),
+            g[3].clone(),
+            g[1].clone(),
+            g[4].clone()
+        ]
+    );
+
+    // A negative cycle 5 -> 6 -> 7 -> 5 that doesn't contain the source.
+    let g = digraph![
+        (usize) => [i64]
+        (0) => [ (5, 1) ]
+        (5) => [ (6, 1) ]
+        (6) => [ (7, -4) ]
+        (7) => [ (5, 1), (8, 1) ]
+        (8) => []
+    ];
+
+    match g.bellman_ford(&0, &|e| *e) {
+        Err(BellmanFordError::NegativeCycle(NegativeCycle(cycle))) => {
+            let sum: i64 = cycle.iter_edges().map(|digraph::Edge(_, _, e)| e).sum();
+            assert!(sum < 0);
+            assert!(cycle.len() == 4);
+            assert!(cycle.first_edge().unwrap().source() == cycle.last_edge().unwrap().target());
+        }
+        _ => panic!("expected a negative cycle"),
+    }
+
+    assert!(g.bellman_ford(&8, &|e| *e).is_ok());
+    assert!(matches!(
+        g.bellman_ford(&9, &|e| *e),
+        Err(BellmanFordError::SourceNotFound)
+    ));
+}
+
+#[test]
+fn ut_digraph_bellman_ford_error() {
+    use gdsl::{error::Error, *};
+
+    // Both failures convert into the crate error with `?`.
+    fn distance(
+        g: &digraph::Graph<usize, (), i64>,
+        source: usize,
+        target: usize,
+    ) -> Result<Option<i64>, Error> {
+        Ok(g.bellman_ford(&source, &|e| *e)?.distance(&target))
+    }
+
+    let g = digraph![
+        (usize) => [i64]
+        (0) => [ (1, 2) ]
+        (1) => [ (2, -1) ]
+        (2) => []
+        (3) => [ (4, -1) ]
+        (4) => [ (3, -1) ]
+    ];
+
+    assert!(matches!(distance(&g, 0, 2), Ok(Some(1))));
+    assert!(matches!(distance(&g, 3, 4), Err(Error::NegativeCycle)));
+    assert!(matches!(distance(&g, 5, 0), Err(Error::NodeNotFound)));
+}
+
+#[test]
+fn ut_digraph_bellman_ford_agrees_with_dijkstra() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut g = Graph::<usize, (), u64>::new();
+
+    for i in 0..50 {
+        g.insert(Node::new(i, ()));
+    }
+    for _ in 0..300 {
+        let (u, v) = (rng.gen_range(0..50), rng.gen_range(0..50));
+        g[u].connect(&g[v], rng.gen_range(0..100));
+    }
+
+    let a = g.shortest_paths(&0, &|e| *e).unwrap();
+    let b = g.bellman_ford(&0, &|e| *e).unwrap();
+
+    for i in 0..50 {
+        assert!(a.distance(&i) == b.distance(&i));
+    }
+}
+
+#[test]
+fn ut_digraph_all_pairs_shortest_paths() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut g = Graph::<usize, (), i64>::new();
+
+    for i in 0..30 {
+        g.insert(Node::new(i, ()));
+    }
+    // Edges only point forward so negative weights can't form a cycle.
+    for _ in 0..150 {
+        let u = rng.gen_range(0..29);
+        let v = rng.gen_range(u + 1..30);
+        g[u].connect(&g[v], rng.gen_range(-10..50));
+    }
+
+    let fw = g.floyd_warshall(&|e| *e).unwrap();
+    let jo = g.johnson(&|e| *e).unwrap();
+
+    assert!(fw.len() == 30);
+    for s in 0..30 {
+        let bf = g.bellman_ford(&s, &|e| *e).unwrap();
+        for t in 0..30 {
+            assert!(fw.distance(&s, &t) == bf.distance(&t));
+            assert!(jo.distance(&s, &t) == bf.distance(&t));
+            for apsp in [&fw, &jo] {
+                match apsp.path(&s, &t) {
+                    Some(path) => {
+                        let len: i64 = path.iter_edges().map(|Edge(_, _, e)| e).sum();
+                        assert!(Some(len) == bf.distance(&t));
+                        assert!(path.first_edge().unwrap().source() == &g[s]);
+                        assert!(path.last_edge().unwrap().target() == &g[t]);
+                    }
+                    None => assert!(s == t || bf.distance(&t).is_none()),
+                }
+            }
+        }
+    }
+
+    // The random edges may not lead from 0 to 29, close the cycle directly.
+    g[0].connect(&g[29], 0);
+    g[29].connect(&g[0], -10_000);
+
+    for result in [g.floyd_warshall(&|e| *e).err(), g.johnson(&|e| *e).err()] {
+        let NegativeCycle(cycle) = result.unwrap();
+        let len: i64 = cycle.iter_edges().map(|Edge(_, _, e)| e).sum();
+        assert!(len < 0);
+        assert!(cycle.first_edge().unwrap().source() == cycle.last_edge().unwrap().target());
+        for w in cycle.edges.windows(2) {
+            assert!(w[0].target() == w[1].source());
+        }
+    }
+}
+
+#[test]
+fn ut_digraph_tarjan_scc() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..20 {
+        let mut g = Graph::<usize, (), ()>::new();
+        for i in 0..50 {
+            g.insert(Node::new(i, ()));
+        }
+        for _ in 0..70 {
+            let (u, v) = (rng.gen_range(0..50), rng.gen_range(0..50));
+            g[u].connect(&g[v], ());
+        }
+
+        // Two nodes share a component if they can reach each other.
+        let reach = (0..50)
+            .map(|i| {
+                let mut r = g[i]
+                    .preorder()
+                    .search_nodes()
+                    .iter()
+                    .map(|n| *n.key())
+                    .collect::<Vec<_>>();
+                r.sort();
+                r
+            })
+            .collect::<Vec<_>>();
+        let mut expected = (0..50)
+            .map(|i| {
+                reach[i]
+                    .iter()
+                    .copied()
+                    .filter(|&j| reach[j].binary_search(&i).is_ok())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let mut tarjan = g
+            .tarjan_scc()
+            .into_iter()
+            .map(|c| {
+                let mut c = c.iter().map(|n| *n.key()).collect::<Vec<_>>();
+                c.sort();
+                c
+            })
+            .collect::<Vec<_>>();
+        expected.sort();
+        expected.dedup();
+        tarjan.sort();
+        assert!(tarjan == expected);
+
+        let dag = g.condensation();
+
+        assert!(dag.len() == expected.len());
+        assert!(dag.iter().map(|(_, n)| n.value().len()).sum::<usize>() == 50);
+
+        // Components are in reverse topological order, so every edge of the
+        // condensation points to a lower index.
+        for (_, node) in dag.iter() {
+            for Edge(u, v, _) in node {
+                assert!(v.key() < u.key());
+            }
+        }
+        for (_, node) in g.iter() {
+            for Edge(u, v, _) in node {
+                let cu = dag.iter().find(|(_, c)| c.value().contains(&u)).unwrap().0;
+                let cv = dag.iter().find(|(_, c)| c.value().contains(&v)).unwrap().0;
+                assert!(cu == cv || dag[*cu].is_connected(cv));
+            }
+        }
+    }
+}
+
+#[test]
+fn ut_digraph_topological_sort() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut g = Graph::<usize, (), ()>::new();
+
+    for i in 0..40 {
+        g.insert(Node::new(i, ()));
+    }
+    for _ in 0..100 {
+        let u = rng.gen_range(0..39);
+        let v = rng.gen_range(u + 1..40);
+        g[u].connect(&g[v], ());
+    }
+
+    let order = g.topological_sort().unwrap();
+    let position = order
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (*n.key(), i))
+        .collect::<std::collections::HashMap<_, _>>();
+
+    assert!(order.len() == 40);
+    for (_, node) in g.iter() {
+        for Edge(u, v, _) in node {
+            assert!(position[u.key()] < position[v.key()]);
+        }
+    }
+
+    let layers = g.topological_layers().unwrap();
+    let depth = layers
+        .iter()
+        .enumerate()
+        .flat_map(|(i, layer)| layer.iter().map(move |n| (*n.key(), i)))
+        .collect::<std::collections::HashMap<_, _>>();
+
+    assert!(depth.len() == 40);
+    for (_, node) in g.iter() {
+        let d = depth[node.key()];
+        for Edge(u, v, _) in node.iter_in() {
+            assert!(depth[u.key()] < d);
+            assert!(depth[v.key()] == d);
+        }
+        // A node is placed right after its deepest predecessor.
+        match node.iter_in().map(|Edge(u, _, _)| depth[u.key()]).max() {
+            Some(max) => assert!(d == max + 1),
+            None => assert!(d == 0),
+        }
+    }
+
+    g[39].connect(&g[38], ());
+    g[38].connect(&g[39], ());
+
+    match g.topological_sort() {
+        Err(Cycle(cycle)) => {
+            assert!(cycle.len() == 3);
+            assert!(cycle.first_edge().unwrap().source() == cycle.last_node().unwrap());
+        }
+        _ => panic!("expected a cycle"),
+    }
+    assert!(matches!(g.topological_layers(), Err(Cycle(_))));
+
+    // An inbound edge from a node outside of the graph that shares a key
+    // with a node in the cycle is not part of the graph, even when it comes
+    // first in the adjacency list.
+    let outsider = Node::new(38, ());
+    outsider.connect(&g[39], ());
+    g[38].disconnect(&39).unwrap();
+    g[38].connect(&g[39], ());
+
+    match g.topological_sort() {
+        Err(Cycle(cycle)) => {
+            assert!(cycle.len() == 3);
+            for Edge(u, v, _) in cycle.iter_edges() {
+                assert!(u.is_connected(v.key()) && g.contains(u.key()));
+            }
+        }
+        _ => panic!("expected a cycle"),
+    }
+}
+
+#[test]
+fn ut_digraph_topological_sort_error() {
+    use gdsl::{error::Error, *};
+
+    // A cycle converts into the crate error with `?`.
+    fn first(g: &digraph::Graph<char, (), ()>) -> Result<char, Error> {
+        Ok(*g.topological_sort()?[0].key())
+    }
+
+    let g = digraph![
+        (char)
+        ('A') => ['B']
+        ('B') => ['C']
+        ('C') => []
+    ];
+
+    assert!(matches!(first(&g), Ok('A')));
+    g['C'].connect(&g['B'], ());
+    assert!(matches!(first(&g), Err(Error::Cycle)));
+}
+
+#[test]
+fn ut_digraph_max_flow() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..50 {
+        let n = 12;
+        let mut g = Graph::<usize, (), u64>::new();
+        for i in 0..n {
+            g.insert(Node::new(i, ()));
+        }
+        for _ in 0..rng.gen_range(10..50) {
+            let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+            g[u].connect(&g[v], rng.gen_range(0..20));
+        }
+
+        let ek = g.edmonds_karp(&0, &(n - 1), &|e| *e).unwrap();
+        let dinic = g.dinic(&0, &(n - 1), &|e| *e).unwrap();
+
+        assert!(ek.value() == dinic.value());
+        for flow in [&ek, &dinic] {
+            // Flow never exceeds capacity and is conserved at every node
+            // except the source and the sink.
+            let mut balance = vec![0i64; n];
+            for (Edge(u, v, c), f) in flow.flows() {
+                assert!(f <= c);
+                balance[*u.key()] -= *f as i64;
+                balance[*v.key()] += *f as i64;
+            }
+            assert!(balance[0] == -(flow.value() as i64));
+            assert!(balance[n - 1] == flow.value() as i64);
+            assert!(balance[1..n - 1].iter().all(|&b| b == 0));
+
+            let cut = flow.cut_edges();
+            assert!(cut.iter().map(|e| *e.value()).sum::<u64>() == flow.value());
+            assert!(flow.source_side().contains(&g[0]));
+            assert!(flow.sink_side().contains(&g[n - 1]));
+            assert!(flow.source_side().len() + flow.sink_side().len() == n);
+        }
+    }
+
+    assert!(Graph::<usize, (), u64>::new()
+        .max_flow(&0, &1, &|e| *e)
+        .is_none());
+
+    // A path this long would overflow the stack of a recursive search.
+    let n = 200_000;
+    let mut g = Graph::<usize, (), u64>::new();
+    for i in 0..n {
+        g.insert(Node::new(i, ()));
+    }
+    for i in 1..n {
+        g[i - 1].connect(&g[i], (i % 7 + 1) as u64);
+    }
+    assert!(g.dinic(&0, &(n - 1), &|e| *e).unwrap().value() == 1);
+}
+
+#[test]
+fn ut_digraph_centrality() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let n = 12;
+    let mut g = Graph::<usize, (), f64>::new();
+    let mut edges = vec![];
+    for i in 0..n {
+        g.insert(Node::new(i, ()));
+    }
+    for _ in 0..30 {
+        let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+        if u != v && !g[u].is_connected(&v) {
+            g[u].connect(&g[v], rng.gen_range(0.5..2.0));
+            edges.push((u, v));
+        }
+    }
+
+    // Shortest path counts and distances with a breadth-first search.
+    let bfs = |s: usize| {
+        let mut dist = vec![usize::MAX; n];
+        let mut count = vec![0.0; n];
+        let mut queue = std::collections::VecDeque::from([s]);
+        dist[s] = 0;
+        count[s] = 1.0;
+        while let Some(u) = queue.pop_front() {
+            for &(a, b) in &edges {
+                if a == u {
+                    if dist[b] == usize::MAX {
+                        dist[b] = dist[u] + 1;
+                        queue.push_back(b);
+                    }
+                    if dist[b] == dist[u] + 1 {
+                        count[b] += count[u];
+                    }
+                }
+            }
+        }
+        (dist, count)
+    };
+    let table = (0..n).map(bfs).collect::<Vec<_>>();
+
+    let betweenness = g.betweenness_centrality();
+    for v in 0..n {
+        let mut expected = 0.0;
+        for s in (0..n).filter(|&s| s != v) {
+            for t in (0..n).filter(|&t| t != v && t != s) {
+                let (dist, count) = &table[s];
+                if dist[t] != usize::MAX
+                    && dist[v] != usize::MAX
+                    && table[v].0[t] != usize::MAX
+                    && dist[v] + table[v].0[t] == dist[t]
+                {
+                    expected += count[v] * table[v].1[t] / count[t];
+                }
+            }
+        }
+        expected /= ((n - 1) * (n - 2)) as f64;
+        assert!((betweenness[&v] - expected).abs() < 1e-9);
+    }
+
+    let closeness = g.closeness_centrality();
+    for u in 0..n {
+        let reached = table[u].0.iter().filter(|&&d| d != usize::MAX);
+        let (r, total) = reached.fold((0, 0), |(r, t), d| (r + 1, t + d));
+        let expected = match total {
+            0 => 0.0,
+            _ => (r - 1) as f64 / total as f64 * (r - 1) as f64 / (n - 1) as f64,
+        };
+        assert!((closeness[&u] - expected).abs() < 1e-9);
+    }
+
+    let degree = g.degree_centrality();
+    let in_degree = g.in_degree_centrality();
+    let out_degree = g.out_degree_centrality();
+    for u in 0..n {
+        assert!(in_degree[&u] == g[u].in_degree() as f64 / (n - 1) as f64);
+        assert!(out_degree[&u] == g[u].out_degree() as f64 / (n - 1) as f64);
+        assert!((degree[&u] - in_degree[&u] - out_degree[&u]).abs() < 1e-12);
+    }
+
+    // The ranks are a fixed point of the PageRank iteration.
+    let personalization = (0..n)
+        .map(|i| (i, (i + 1) as f64))
+        .collect::<std::collections::HashMap<_, _>>();
+    let rank = g
+        .page_rank()
+        .damping(0.8)
+        .tolerance(1e-12)
+        .max_iterations(1000)
+        .weight(&|e| *e)
+        .personalization(personalization)
+        .compute();
+    let teleport = |v: usize| (v + 1) as f64 / (n * (n + 1) / 2) as f64;
+    let dangling = (0..n)
+        .filter(|&u| g[u].out_degree() == 0)
+        .map(|u| rank[&u])
+        .sum::<f64>();
+    assert!((rank.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    for v in 0..n {
+        let mut expected = (0.2 + 0.8 * dangling) * teleport(v);
+        for Edge(u, _, w) in g[v].iter_in() {
+            let total = u.iter_out().map(|Edge(_, _, w)| w).sum::<f64>();
+            expected += 0.8 * rank[u.key()] * w / total;
+        }
+        assert!((rank[&v] - expected).abs() < 1e-9);
+    }
+
+    // A personalization without any weight falls back to the uniform one.
+    let zeros = (0..n).map(|i| (i, 0.0)).collect();
+    let rank = g.page_rank().personalization(zeros).compute();
+    let uniform = g.page_rank().compute();
+    for v in 0..n {
+        assert!(rank[&v].is_finite() && rank[&v] == uniform[&v]);
+    }
+    assert!(Graph::<usize, (), ()>::new()
+        .page_rank()
+        .compute()
+        .is_empty());
+
+    // A cycle is symmetric so every node is equally central.
+    let c = gdsl::digraph![
+        (usize)
+        (0) => [1]
+        (1) => [2]
+        (2) => [3]
+        (3) => [0]
+    ];
+    let rank = c.page_rank().compute();
+    let eigen = c.eigenvector_centrality(1e-12, 1000).unwrap();
+    for i in 0..4 {
+        assert!((rank[&i] - 0.25).abs() < 1e-6);
+        assert!((eigen[&i] - 0.5).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn ut_digraph_isomorphism() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn count(g: &Graph<usize, (), u8>, u: usize, v: usize) -> usize {
+        g[u].iter_out()
+            .filter(|Edge(_, w, _)| *w.key() == v)
+            .count()
+    }
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..30 {
+        // A random graph and a relabeled copy of it.
+        let mut g = Graph::<usize, (), u8>::new();
+        let mut h = Graph::<usize, (), u8>::new();
+        let mut perm = (0..6).collect::<Vec<usize>>();
+        for i in (1..6).rev() {
+            perm.swap(i, rng.gen_range(0..=i));
+        }
+        for (i, j) in perm.iter().enumerate() {
+            g.insert(Node::new(i, ()));
+            h.insert(Node::new(100 + j, ()));
+        }
+        for _ in 0..rng.gen_range(0..14) {
+            let (u, v, e) = (
+                rng.gen_range(0..6),
+                rng.gen_range(0..6),
+                rng.gen_range(0..2),
+            );
+            g[u].connect(&g[v], e);
+            h[100 + perm[u]].connect(&h[100 + perm[v]], e);
+        }
+
+        assert!(g.is_isomorphic(&h));
+        let same = |a: &u8, b: &u8| a == b;
+        let mappings = g
+            .isomorphism(&h)
+            .edge_match(&same)
+            .mappings()
+            .collect::<Vec<_>>();
+        assert!(!mappings.is_empty());
+        for m in &mappings {
+            for u in 0..6 {
+                for v in 0..6 {
+                    assert!(count(&h, u + 100, v + 100) == count(&g, m[&(u + 100)], m[&(v + 100)]));
+                }
+            }
+        }
+
+        let (u, v) = (rng.gen_range(0..6), rng.gen_range(0..6));
+        h[100 + perm[u]].connect(&h[100 + perm[v]], 0);
+        assert!(!g.is_isomorphic(&h));
+
+        // Induced subgraph on the first four nodes of the copy, compared to
+        // a brute force over every injective mapping.
+        let mut p = Graph::<usize, (), u8>::new();
+        for i in 0..4 {
+            p.insert(Node::new(100 + i, ()));
+        }
+        for i in 0..4 {
+            for Edge(_, v, e) in h[100 + i].iter_out() {
+                if *v.key() < 104 {
+                    p[100 + i].connect(&p[*v.key()], e);
+                }
+            }
+        }
+        let mut expected = 0;
+        for a in 0..6 {
+            for b in 0..6 {
+                for c in 0..6 {
+                    for d in 0..6 {
+                        let m = [a, b, c, d];
+                        if (0..4).any(|i| m[..i].contains(&m[i])) {
+                            continue;
+                        }
+                        let ok = (0..4).all(|i| {
+                            (0..4).all(|j| {
+                                count(&p, 100 + i, 100 + j) == count(&h, 100 + m[i], 100 + m[j])
+                            })
+                        });
+                        if ok {
+                            expected += 1;
+                        }
+                    }
+                }
+            }
+        }
+        assert!(h.subgraph_mappings(&p).count() == expected);
+        assert!(h.is_subgraph_isomorphic(&p));
+    }
+}
+
+#[test]
+fn ut_digraph_isomorphism_parallel_edges() {
+    use gdsl::digraph::*;
+
+    // A path 0 - 1 - 2 with the given parallel edges between 0 and 1.
+    fn build(parallel: [char; 3]) -> Graph<usize, (), char> {
+        let mut g = Graph::new();
+        for i in 0..3 {
+            g.insert(Node::new(i, ()));
+        }
+        for e in parallel {
+            g[0].connect(&g[1], e);
+        }
+        g[1].connect(&g[2], 'a');
+        g
+    }
+
+    let same = |a: &char, b: &char| a == b;
+
+    // The same parallel edges connected in a different order.
+    let g = build(['a', 'b', 'c']);
+    let h = build(['c', 'a', 'b']);
+    assert!(g.isomorphism(&h).edge_match(&same).is_isomorphic());
+    assert!(g.isomorphism(&h).edge_match(&same).is_subgraph_isomorphic());
+    let mut mappings = g.isomorphism(&h).edge_match(&same).mappings();
+    let mapping: std::collections::HashMap<usize, usize> = mappings.next().unwrap();
+    assert!((0..3).all(|i| mapping[&i] == i));
+
+    // The same number of parallel edges with other values.
+    let h = build(['b', 'a', 'b']);
+    assert!(g.is_isomorphic(&h));
+    assert!(!g.isomorphism(&h).edge_match(&same).is_isomorphic());
+}
+
+#[test]
+fn ut_digraph_cycles() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    // Count the simple cycles of at most `max` edges whose smallest node is
+    // the first node of `path`.
+    fn brute(adj: &[Vec<bool>], path: &mut Vec<usize>, max: usize) -> usize {
+        let (s, u) = (path[0], *path.last().unwrap());
+        let mut count = 0;
+        for v in s..adj.len() {
+            if !adj[u][v] {
+                continue;
+            }
+            if v == s {
+                count += (path.len() <= max) as usize;
+            } else if !path.contains(&v) && path.len() < max {
+                path.push(v);
+                count += brute(adj, path, max);
+                path.pop();
+            }
+        }
+        count
+    }
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..50 {
+        let mut g = Graph::<usize, (), ()>::new();
+        let mut adj = vec![vec![false; 7]; 7];
+        for i in 0..7 {
+            g.insert(Node::new(i, ()));
+        }
+        for _ in 0..rng.gen_range(0..20) {
+            let (u, v) = (rng.gen_range(0..7), rng.gen_range(0..7));
+            g[u].connect(&g[v], ());
+            adj[u][v] = true;
+        }
+
+        let cycles = g.cycles().collect::<Vec<_>>();
+        let expected = (0..7).map(|s| brute(&adj, &mut vec![s], 7)).sum::<usize>();
+        assert!(cycles.len() == expected);
+
+        let mut seen = std::collections::HashSet::new();
+        for cycle in &cycles {
+            let mut nodes = cycle
+                .iter_edges()
+                .map(|Edge(u, _, _)| *u.key())
+                .collect::<Vec<_>>();
+            assert!(cycle
+                .iter_edges()
+                .all(|Edge(u, v, _)| adj[*u.key()][*v.key()]));
+            assert!(cycle.first_edge().unwrap().source() == cycle.last_node().unwrap());
+            let first = (0..nodes.len()).min_by_key(|&i| nodes[i]).unwrap();
+            nodes.rotate_left(first);
+            assert!(seen.insert(nodes));
+        }
+
+        let max = rng.gen_range(1..5);
+        let short = g.cycles().max_length(max).collect::<Vec<_>>();
+        let expected = (0..7)
+            .map(|s| brute(&adj, &mut vec![s], max))
+            .sum::<usize>();
+        assert!(short.len() == expected);
+        assert!(short.iter().all(|c| c.edges.len() <= max));
+    }
+}
+
+#[test]
+fn ut_digraph_simple_and_k_shortest_paths() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    // Costs of every simple path from the last node of `path` to `target`.
+    fn brute(
+        adj: &[Vec<(usize, u32)>],
+        path: &mut Vec<usize>,
+        cost: u32,
+        target: usize,
+        out: &mut Vec<(usize, u32)>,
+    ) {
+        let u = *path.last().unwrap();
+        for &(v, w) in &adj[u] {
+            if v == target {
+                out.push((path.len(), cost + w));
+            } else if !path.contains(&v) {
+                path.push(v);
+                brute(adj, path, cost + w, target, out);
+                path.pop();
+            }
+        }
+    }
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..50 {
+        let n = (0..6).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+        let mut adj = vec![vec![]; 6];
+        for _ in 0..rng.gen_range(0..16) {
+            let (u, v, w) = (
+                rng.gen_range(0..6),
+                rng.gen_range(0..6),
+                rng.gen_range(0..5),
+            );
+            n[u].connect(&n[v], w);
+            adj[u].push((v, w));
+        }
+
+        let mut expected = vec![];
+        brute(&adj, &mut vec![0], 0, 5, &mut expected);
+
+        let paths = n[0].all_simple_paths(&5, None).collect::<Vec<_>>();
+        assert!(paths.len() == expected.len());
+        for path in &paths {
+            let nodes = path.to_vec_nodes();
+            assert!(nodes[0] == n[0] && nodes[nodes.len() - 1] == n[5]);
+            assert!((1..nodes.len()).all(|i| !nodes[..i].contains(&nodes[i])));
+        }
+
+        let depth = rng.gen_range(1..5);
+        let short = n[0].all_simple_paths(&5, Some(depth)).count();
+        assert!(short == expected.iter().filter(|(len, _)| *len <= depth).count());
+
+        let ranked = n[0].k_shortest_paths(&5, &|e| *e).collect::<Vec<_>>();
+        let mut costs = expected.iter().map(|(_, cost)| *cost).collect::<Vec<_>>();
+        costs.sort();
+        assert!(ranked.iter().map(|(_, cost)| *cost).collect::<Vec<_>>() == costs);
+        for (path, cost) in &ranked {
+            assert!(path.iter_edges().map(|Edge(_, _, e)| e).sum::<u32>() == *cost);
+        }
+    }
+}
+
+#[test]
+fn ut_digraph_traversal_iterators() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..30 {
+        let n = (0..8).map(|i| Node::new(i, i)).collect::<Vec<_>>();
+        for _ in 0..rng.gen_range(0..20) {
+            n[rng.gen_range(0..8)].connect(&n[rng.gen_range(0..8)], ());
+        }
+        let reached = n[0].preorder().search_nodes().len();
+
+        // Without a target every search visits every reachable node once.
+        assert!(n[0].bfs().count() == reached - 1);
+        assert!(n[0].dfs().count() == reached - 1);
+        assert!(n[0].pfs().count() == reached - 1);
+        assert!(n[0].dfs().collect::<Vec<_>>() == n[0].preorder().search_edges());
+
+        // With a target the iteration ends at the edge reaching it.
+        let target = rng.gen_range(1..8);
+        let searches = [
+            (
+                n[0].bfs().target(&target).collect::<Vec<_>>(),
+                n[0].bfs().target(&target).search_path(),
+            ),
+            (
+                n[0].dfs().target(&target).collect::<Vec<_>>(),
+                n[0].dfs().target(&target).search_path(),
+            ),
+            (
+                n[0].pfs().target(&target).collect::<Vec<_>>(),
+                n[0].pfs().target(&target).search_path(),
+            ),
+        ];
+        for (edges, path) in searches {
+            let last = edges.last().map(|e| *e.target().key());
+            assert!(path.is_some() == (last == Some(target)));
+            if let Some(path) = path {
+                assert!(Path::from_edge_tree(edges).edges == path.edges);
+            }
+        }
+
+        // In post-order an edge comes after every edge below its target.
+        let post = n[0].postorder().collect::<Vec<_>>();
+        assert!(post.len() == n[0].preorder().transpose().count());
+        for (i, Edge(_, v, _)) in post.iter().enumerate() {
+            assert!(post[i..].iter().all(|Edge(u, _, _)| u != v));
+        }
+    }
+
+    let n = (0..6).map(|i| Node::new(i, i)).collect::<Vec<_>>();
+    for i in 0..6 {
+        n[i].connect(&n[(i + 1) % 6], ());
+    }
+
+    let keys = n[0]
+        .bfs()
+        .take_while(|e| *e.target().key() != 4)
+        .map(|e| *e.target().key());
+    assert!(keys.collect::<Vec<_>>() == vec![1, 2, 3]);
+
+    for (a, b) in n[0].dfs().zip(n[0].pfs()) {
+        assert!(a == b);
+    }
+
+    let mut search = n[0].dfs();
+    let first = search.search_cycle();
+    let second = search.search_cycle();
+    assert!(first.unwrap().len() == second.unwrap().len());
+}
+
+#[test]
+fn ut_digraph_depth_limited_search() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..50 {
+        let n = (0..8).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+        let mut adj = vec![vec![]; 8];
+        for _ in 0..rng.gen_range(0..14) {
+            let (u, v) = (rng.gen_range(0..8), rng.gen_range(0..8));
+            n[u].connect(&n[v], ());
+            adj[u].push(v);
+        }
+
+        // Distances from the root by a plain breadth-first search.
+        let mut dist = [usize::MAX; 8];
+        let mut queue = std::collections::VecDeque::from([0]);
+        dist[0] = 0;
+        while let Some(u) = queue.pop_front() {
+            for &v in &adj[u] {
+                if dist[v] == usize::MAX {
+                    dist[v] = dist[u] + 1;
+                    queue.push_back(v);
+                }
+            }
+        }
+
+        let max = rng.gen_range(0..4);
+        let within = (1..8).filter(|&v| dist[v] <= max).collect::<Vec<_>>();
+        let targets = |edges: Vec<Edge<usize, (), ()>>| {
+            let mut keys = edges.iter().map(|e| *e.target().key()).collect::<Vec<_>>();
+            keys.sort();
+            keys
+        };
+
+        assert!(targets(n[0].bfs().max_depth(max).collect()) == within);
+        assert!(targets(n[0].dfs().max_depth(max).collect()) == within);
+        assert!(
+            targets(
+                n[0].bfs()
+                    .filter_depth(&mut |_, depth| depth <= max)
+                    .collect()
+            ) == within
+        );
+
+        let mut depths = vec![];
+        let edges = n[0]
+            .dfs()
+            .iterative_deepening()
+            .max_depth(max)
+            .for_each_depth(&mut |_, depth| depths.push(depth))
+            .collect::<Vec<_>>();
+        assert!(depths.iter().all(|&depth| depth <= max));
+        let order = edges
+            .iter()
+            .map(|e| dist[*e.target().key()])
+            .collect::<Vec<_>>();
+        assert!(order.windows(2).all(|w| w[0] <= w[1]));
+        assert!(edges
+            .iter()
+            .all(|e| dist[*e.source().key()] + 1 == dist[*e.target().key()]));
+        assert!(targets(edges) == within);
+
+        let all = (1..8)
+            .filter(|&v| dist[v] != usize::MAX)
+            .collect::<Vec<_>>();
+        assert!(targets(n[0].dfs().iterative_deepening().collect()) == all);
+
+        let count = n[0]
+            .bfs()
+            .for_each_depth(&mut |Edge(u, _, _), depth| {
+                assert!(dist[*u.key()] + 1 == depth);
+            })
+            .count();
+        assert!(count == all.len());
+    }
+
+    // Every pass starts over from the root, so the closure sees the edge
+    // leaving the root once per pass, four times for a path of three edges.
+    let n = (0..4).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+    for i in 0..3 {
+        n[i].connect(&n[i + 1], ());
+    }
+    let mut calls = 0;
+    let edges = n[0]
+        .dfs()
+        .iterative_deepening()
+        .for_each(&mut |Edge(u, _, _)| {
+            if *u.key() == 0 {
+                calls += 1;
+            }
+        })
+        .count();
+    assert!(edges == 3);
+    assert!(calls == 4);
+}
+
+#[test]
+fn ut_digraph_bidirectional_search() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..100 {
+        let n = (0..10).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+        for _ in 0..rng.gen_range(0..30) {
+            let (u, v) = (rng.gen_range(0..10), rng.gen_range(0..10));
+            n[u].connect(&n[v], rng.gen_range(0..10u32));
+        }
+        let (s, t) = (rng.gen_range(0..10), rng.gen_range(0..10));
+
+        let expected = n[s].bfs().target(&t).search_path();
+        let path = n[s].bidirectional(&n[t]).search_path();
+        assert!(path.as_ref().map(|p| p.len()) == expected.map(|p| p.len()));
+        if let Some(path) = path {
+            let nodes = path.to_vec_nodes();
+            assert!(nodes[0] == n[s] && nodes[nodes.len() - 1] == n[t]);
+            assert!(path
+                .iter_edges()
+                .all(|Edge(u, v, _)| u.is_connected(v.key())));
+        }
+
+        let distance = n[s].dijkstra(&|e| *e).search().distance(&t);
+        let weighted = n[s].bidirectional(&n[t]).search_weighted(&|e| *e);
+        match s == t {
+            true => assert!(weighted.is_none()),
+            false => assert!(weighted.as_ref().map(|(_, cost)| *cost) == distance),
+        }
+        if let Some((path, cost)) = weighted {
+            assert!(path.iter_edges().map(|Edge(_, _, e)| e).sum::<u32>() == cost);
+            let nodes = path.to_vec_nodes();
+            assert!(nodes[0] == n[s] && nodes[nodes.len() - 1] == n[t]);
+            assert!((1..nodes.len()).all(|i| nodes[i - 1].is_connected(nodes[i].key())));
+        }
+    }
+}
+
+#[test]
+fn ut_digraph_multi_source_search() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..50 {
+        let n = (0..10).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+        let mut adj = vec![vec![]; 10];
+        for _ in 0..rng.gen_range(0..16) {
+            let (u, v) = (rng.gen_range(0..10), rng.gen_range(0..10));
+            n[u].connect(&n[v], ());
+            adj[u].push(v);
+        }
+        let seeds = (0..rng.gen_range(1..4))
+            .map(|_| rng.gen_range(0..10))
+            .collect::<Vec<usize>>();
+
+        // Distances from every node by a plain breadth-first search.
+        let dist = (0..10)
+            .map(|s| {
+                let mut dist = [usize::MAX; 10];
+                let mut queue = std::collections::VecDeque::from([s]);
+                dist[s] = 0;
+                while let Some(u) = queue.pop_front() {
+                    for &v in &adj[u] {
+                        if dist[v] == usize::MAX {
+                            dist[v] = dist[u] + 1;
+                            queue.push_back(v);
+                        }
+                    }
+                }
+                dist
+            })
+            .collect::<Vec<_>>();
+        let nearest = |v: usize| seeds.iter().map(|&s| dist[s][v]).min().unwrap();
+        let reached = (0..10)
+            .filter(|&v| !seeds.contains(&v) && nearest(v) != usize::MAX)
+            .collect::<Vec<_>>();
+        let roots = || seeds.iter().map(|&s| n[s].clone());
+        let targets = |edges: &[Edge<usize, (), ()>]| {
+            let mut keys = edges.iter().map(|e| *e.target().key()).collect::<Vec<_>>();
+            keys.sort();
+            keys
+        };
+
+        let mut bfs = Bfs::from_roots(roots());
+        let edges = bfs.by_ref().collect::<Vec<_>>();
+        assert!(targets(&edges) == reached);
+        let mut depth = [0; 10];
+        for Edge(u, v, _) in &edges {
+            depth[*v.key()] = depth[*u.key()] + 1;
+            assert!(depth[*v.key()] == nearest(*v.key()));
+            let origin = *bfs.origin(v.key()).unwrap();
+            assert!(seeds.contains(&origin) && dist[origin][*v.key()] == depth[*v.key()]);
+        }
+        assert!(seeds.iter().all(|s| bfs.origin(s) == Some(s)));
+
+        let mut dfs = Dfs::from_roots(roots());
+        let edges = dfs.by_ref().collect::<Vec<_>>();
+        assert!(targets(&edges) == reached);
+        for v in &reached {
+            let origin = *dfs.origin(v).unwrap();
+            assert!(seeds.contains(&origin) && dist[origin][*v] != usize::MAX);
+        }
+
+        let mut pfs = Pfs::from_roots(roots());
+        let edges = pfs.by_ref().collect::<Vec<_>>();
+        assert!(targets(&edges) == reached);
+        let distinct = seeds.iter().collect::<std::collections::HashSet<_>>().len();
+        let origins: &std::collections::HashMap<usize, usize> = pfs.origins();
+        assert!(origins.len() == reached.len() + distinct);
+
+        let t = rng.gen_range(0..10);
+        let path = Bfs::from_roots(roots()).target(&t).search_path();
+        match reached.contains(&t) {
+            true => {
+                let path = path.unwrap();
+                assert!(seeds.contains(path.first_edge().unwrap().source().key()));
+                assert!(path.len() == nearest(t) + 1);
+            }
+            false => assert!(path.is_none()),
+        }
+    }
+}
+
+#[test]
+fn ut_digraph_dfs_visitor() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Discover(usize),
+        Finish(usize),
+        Tree(usize, usize),
+        Back(usize, usize),
+        ForwardOrCross(usize, usize),
+    }
+
+    struct Recorder {
+        events: Vec<Event>,
+        prune: usize,
+        stop: bool,
+    }
+
+    impl Visitor<usize, (), ()> for Recorder {
+        fn discover_node(&mut self, node: &Node<usize, (), ()>) -> Control {
+            self.events.push(Event::Discover(*node.key()));
+            match *node.key() == self.prune {
+                true => Control::Prune,
+                false => Control::Continue,
+            }
+        }
+
+        fn finish_node(&mut self, node: &Node<usize, (), ()>) -> Control {
+            self.events.push(Event::Finish(*node.key()));
+            Control::Continue
+        }
+
+        fn tree_edge(&mut self, Edge(u, v, _): &Edge<usize, (), ()>) -> Control {
+            self.events.push(Event::Tree(*u.key(), *v.key()));
+            Control::Continue
+        }
+
+        fn back_edge(&mut self, Edge(u, v, _): &Edge<usize, (), ()>) -> Control {
+            self.events.push(Event::Back(*u.key(), *v.key()));
+            match self.stop {
+                true => Control::Stop,
+                false => Control::Continue,
+            }
+        }
+
+        fn forward_or_cross_edge(&mut self, Edge(u, v, _): &Edge<usize, (), ()>) -> Control {
+            self.events.push(Event::ForwardOrCross(*u.key(), *v.key()));
+            Control::Continue
+        }
+    }
+
+    // Recursive depth-first search over the adjacency lists.
+    fn reference(
+        adj: &[Vec<usize>],
+        u: usize,
+        prune: usize,
+        state: &mut [u8],
+        events: &mut Vec<Event>,
+    ) {
+        state[u] = 1;
+        events.push(Event::Discover(u));
+        if u != prune {
+            for &v in &adj[u] {
+                match state[v] {
+                    0 => {
+                        events.push(Event::Tree(u, v));
+                        reference(adj, v, prune, state, events);
+                    }
+                    1 => events.push(Event::Back(u, v)),
+                    _ => events.push(Event::ForwardOrCross(u, v)),
+                }
+            }
+        }
+        state[u] = 2;
+        events.push(Event::Finish(u));
+    }
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..50 {
+        let n = (0..10).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+        let mut adj = vec![vec![]; 10];
+        for _ in 0..rng.gen_range(0..20) {
+            let (u, v) = (rng.gen_range(0..10), rng.gen_range(0..10));
+            n[u].connect(&n[v], ());
+            adj[u].push(v);
+        }
+
+        let prune = rng.gen_range(0..20);
+        let mut expected = vec![];
+        let mut state = [0; 10];
+        for u in 0..10 {
+            if state[u] == 0 {
+                reference(&adj, u, prune, &mut state, &mut expected);
+            }
+        }
+
+        let mut recorder = Recorder {
+            events: vec![],
+            prune,
+            stop: false,
+        };
+        let control = Dfs::from_roots(n.iter().cloned()).visit(&mut recorder);
+        assert!(control == Control::Continue);
+        assert!(recorder.events == expected);
+
+        recorder.events.clear();
+        recorder.stop = true;
+        let control = Dfs::from_roots(n.iter().cloned()).visit(&mut recorder);
+        match expected.iter().position(|e| matches!(e, Event::Back(..))) {
+            Some(i) => {
+                assert!(control == Control::Stop);
+                assert!(recorder.events[..] == expected[..=i]);
+            }
+            None => assert!(control == Control::Continue),
+        }
+    }
+}
+
+#[test]
+fn ut_digraph_dominators() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    // Nodes reachable from `root` without passing through `removed`.
+    fn reach(adj: &[Vec<usize>], root: usize, removed: Option<usize>) -> Vec<bool> {
+        let mut seen = vec![false; adj.len()];
+        let mut stack = vec![root];
+        seen[root] = true;
+        while let Some(u) = stack.pop() {
+            for &v in &adj[u] {
+                if !seen[v] && Some(v) != removed {
+                    seen[v] = true;
+                    stack.push(v);
+                }
+            }
+        }
+        seen
+    }
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..100 {
+        let n = (0..8).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+        let mut out = vec![vec![]; 8];
+        let mut inbound = vec![vec![]; 8];
+        for _ in 0..rng.gen_range(0..20) {
+            let (u, v) = (rng.gen_range(0..8), rng.gen_range(0..8));
+            n[u].connect(&n[v], ());
+            out[u].push(v);
+            inbound[v].push(u);
+        }
+        let root = rng.gen_range(0..8);
+
+        for (adj, preds, tree) in [
+            (&out, &inbound, n[root].dominators().search()),
+            (&inbound, &out, n[root].dominators().transpose().search()),
+        ] {
+            let reachable = reach(adj, root, None);
+            // dom[a][b] is true if every path from the root to b visits a.
+            let dom = (0..8)
+                .map(|a| {
+                    let without = reach(adj, root, Some(a));
+                    (0..8)
+                        .map(|b| {
+                            reachable[a] && reachable[b] && (a == b || a == root || !without[b])
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            for b in 0..8 {
+                assert!(tree.contains(&b) == reachable[b]);
+                for (a, dominated) in dom.iter().enumerate() {
+                    assert!(tree.dominates(&a, &b) == dominated[b]);
+                }
+                if !reachable[b] || b == root {
+                    assert!(tree.idom(&b).is_none());
+                    continue;
+                }
+                // The immediate dominator is the strict dominator closest
+                // to b, the one dominated by all the others.
+                let strict = (0..8).filter(|&a| a != b && dom[a][b]).collect::<Vec<_>>();
+                let idom = *tree.idom(&b).unwrap().key();
+                assert!(strict.iter().all(|&a| dom[a][idom]));
+                assert!(tree.idoms()[&b] == idom);
+                let mut chain = tree.dominators(&b).map(|n| *n.key()).collect::<Vec<_>>();
+                chain.sort();
+                assert!(chain == strict);
+            }
+
+            for a in (0..8).filter(|&a| reachable[a]) {
+                let mut frontier = tree.frontier(&a).map(|n| *n.key()).collect::<Vec<_>>();
+                frontier.sort();
+                let expected = (0..8)
+                    .filter(|&b| {
+                        reachable[b]
+                            && preds[b].iter().any(|&p| dom[a][p])
+                            && (a == b || !dom[a][b])
+                    })
+                    .collect::<Vec<_>>();
+                assert!(frontier == expected);
+            }
+
+            let nodes = tree.iter().collect::<Vec<_>>();
+            assert!(nodes.len() == reachable.iter().filter(|&&r| r).count());
+            assert!(*nodes[0].0.key() == root && nodes[0].1 == 0);
+            for (node, depth) in &nodes[1..] {
+                assert!(tree.dominators(node.key()).count() == *depth);
+                let idom = tree.idom(node.key()).unwrap();
+                assert!(tree.children(idom.key()).any(|c| c == *node));
+            }
+        }
+    }
+}
+
+#[test]
+fn ut_digraph_shared_edge_values() {
+    use gdsl::digraph::*;
+
+    let a = Node::new('a', ());
+    let b = Node::new('b', ());
+    let c = Node::new('c', ());
+
+    a.connect(&b, 1);
+    b.connect(&a, 10);
+    b.connect(&c, 100);
+
+    // An update from either end is seen from both ends of the edge.
+    b.update_edge(&'c', |w| *w += 1).unwrap();
+    c.update_edge(&'b', |w| *w += 1).unwrap();
+    assert!(b.edge_value(&'c', |w| *w) == Some(102));
+    assert!(c.edge_value(&'b', |w| *w) == Some(102));
+    assert!(c.iter_in().next().unwrap().value() == &102);
+
+    // Outbound edges are found before inbound ones.
+    a.update_edge(&'b', |w| *w += 1).unwrap();
+    b.update_edge(&'a', |w| *w += 1).unwrap();
+    assert!(a.edge_value(&'b', |w| *w) == Some(2));
+    assert!(b.edge_value(&'a', |w| *w) == Some(11));
+    assert!(a.iter_in().next().unwrap().value() == &11);
+
+    // Nodes that are not adjacent have no edge value.
+    assert!(a.edge_value(&'c', |w| *w).is_none());
+    assert!(a.update_edge(&'c', |w| *w += 1).is_err());
+
+    // The removed value is the shared one.
+    assert!(b.disconnect(&'c').unwrap() == 102);
+    assert!(c.edge_value(&'b', |w| *w).is_none());
+}
+
+#[test]
+fn ut_digraph_remove_and_dangling_edges() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..50 {
+        let mut adj = [[false; 10]; 10];
+        for (u, row) in adj.iter_mut().enumerate() {
+            for (v, edge) in row.iter_mut().enumerate() {
+                *edge = u != v && rng.gen_range(0..4) == 0;
+            }
+        }
+        let alive = (0..10)
+            .map(|_| rng.gen_range(0..3) != 0)
+            .collect::<Vec<_>>();
+        let check = |n: &[Node<usize, (), ()>], adj: &[[bool; 10]; 10]| {
+            for u in n {
+                let mut out = u
+                    .iter_out()
+                    .map(|Edge(_, v, _)| *v.key())
+                    .collect::<Vec<_>>();
+                let mut inb = u
+                    .iter_in()
+                    .map(|Edge(v, _, _)| *v.key())
+                    .collect::<Vec<_>>();
+                out.sort();
+                inb.sort();
+                let expected_out = (0..10)
+                    .filter(|&v| alive[v] && adj[*u.key()][v])
+                    .collect::<Vec<_>>();
+                let expected_in = (0..10)
+                    .filter(|&v| alive[v] && adj[v][*u.key()])
+                    .collect::<Vec<_>>();
+                assert!(out == expected_out && inb == expected_in);
+                assert!(u.out_degree() == out.len() && u.in_degree() == inb.len());
+                for (v, &live) in alive.iter().enumerate() {
+                    if !live {
+                        assert!(!u.is_connected(&v));
+                        assert!(u.disconnect(&v).is_err());
+                    }
+                }
+            }
+        };
+
+        // Removing a node from a graph detaches it from the rest.
+        let mut g = Graph::<usize, (), ()>::new();
+        for i in 0..10 {
+            g.insert(Node::new(i, ()));
+        }
+        for (u, row) in adj.iter().enumerate() {
+            for (v, &edge) in row.iter().enumerate() {
+                if edge {
+                    g[u].connect(&g[v], ());
+                }
+            }
+        }
+        for (u, &live) in alive.iter().enumerate() {
+            if !live {
+                assert!(g.remove(&u).unwrap().is_orphan());
+            }
+        }
+        check(&g.to_vec(), &adj);
+
+        // Dropping a connected node leaves dangling edges which are skipped.
+        let mut n = (0..10).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+        for (u, row) in adj.iter().enumerate() {
+            for (v, &edge) in row.iter().enumerate() {
+                if edge {
+                    n[u].connect(&n[v], ());
+                }
+            }
+        }
+        n.retain(|u| alive[*u.key()]);
+        check(&n, &adj);
+
+        // Connecting more nodes keeps skipping the dangling edges.
+        for u in &n {
+            for v in &n {
+                if u.key() != v.key() && !adj[*u.key()][*v.key()] && rng.gen_range(0..4) == 0 {
+                    u.connect(v, ());
+                    adj[*u.key()][*v.key()] = true;
+                }
+            }
+        }
+        check(&n, &adj);
+        for u in &n {
+            u.isolate();
+        }
+        assert!(n.iter().all(|u| u.is_orphan()));
+    }
+
+    // Connecting during iteration leaves the edges ahead of the cursor in
+    // place, even with a dangling edge behind it, so none is skipped.
+    let n = (0..5).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+    let dropped = Node::new(5, ());
+    n[0].connect(&dropped, ());
+    for v in &n[1..4] {
+        n[0].connect(v, ());
+    }
+    drop(dropped);
+    let mut targets = vec![];
+    for Edge(_, v, _) in n[0].iter_out() {
+        if targets.is_empty() {
+            n[0].connect(&n[4], ());
+        }
+        targets.push(*v.key());
+    }
+    assert!(targets == [1, 2, 3, 4]);
+}
+
+#[test]
+fn ut_digraph_multigraph() {
+    use gdsl::{digraph::*, error::Error};
+
+    let mut g = Graph::<usize, (), u32>::new();
+    g.insert(Node::new(0, ()));
+    g.insert(Node::new(1, ()));
+
+    g[0].connect(&g[1], 10);
+    g[0].connect(&g[1], 20);
+    g[0].connect_keyed(&g[1], 5, 50).unwrap();
+    g[0].connect_keyed(&g[1], 3, 30).unwrap();
+
+    // Keys other than `0` are unique and `0` is reserved for `connect`.
+    assert!(matches!(
+        g[0].connect_keyed(&g[1], 3, 31),
+        Err(Error::EdgeAlreadyExists)
+    ));
+    assert!(matches!(
+        g[0].connect_keyed(&g[1], 0, 0),
+        Err(Error::ReservedKey)
+    ));
+    assert!(g[0].parallel_edges(&1) == vec![(0, 10), (0, 20), (3, 30), (5, 50)]);
+
+    // Parallel edges are directed, the reverse direction has none.
+    assert!(g[1].parallel_edges(&0).is_empty());
+}
+
+#[test]
+fn ut_digraph_multigraph_disconnect_edge() {
+    use gdsl::{digraph::*, error::Error};
+
+    let mut g = Graph::<usize, (), u32>::new();
+    g.insert(Node::new(0, ()));
+    g.insert(Node::new(1, ()));
+
+    g[0].connect(&g[1], 10);
+    g[0].connect_keyed(&g[1], 3, 30).unwrap();
+    g[0].connect_keyed(&g[1], 5, 50).unwrap();
+
+    // Only the edge with the key is removed, from both endpoints.
+    assert!(g[0].disconnect_edge(&1, 3).unwrap() == 30);
+    assert!(matches!(
+        g[0].disconnect_edge(&1, 3),
+        Err(Error::EdgeNotFound)
+    ));
+    assert!(g[0].parallel_edges(&1) == vec![(0, 10), (5, 50)]);
+    assert!(g[1].in_degree() == 2);
+
+    // Edges made with `connect` can't be addressed by key.
+    assert!(matches!(
+        g[0].disconnect_edge(&1, 0),
+        Err(Error::ReservedKey)
+    ));
+    assert!(g[0].disconnect(&1).unwrap() == 10);
+    assert!(g[0].parallel_edges(&1) == vec![(5, 50)]);
+}
+
+#[test]
+fn ut_digraph_multigraph_serde_json() {
+    use gdsl::digraph::*;
+
+    let mut g = Graph::<usize, (), u32>::new();
+    g.insert(Node::new(0, ()));
+    g.insert(Node::new(1, ()));
+
+    g[0].connect(&g[1], 10);
+    g[0].connect_keyed(&g[1], 5, 50).unwrap();
+    g[0].connect_keyed(&g[1], 3, 30).unwrap();
+
+    let json = serde_json::to_string(&g).unwrap();
+    let de: Graph<usize, (), u32> = serde_json::from_str(&json).unwrap();
+    assert!(de[0].parallel_edges(&1) == g[0].parallel_edges(&1));
+
+    // Data serialized before edges had keys still loads. The edge list keeps
+    // that format and the keys other than `0` follow it by edge index.
+    let json = "[[[0,null],[1,null]],[[0,1,7],[0,1,8]]]";
+    let g: Graph<usize, (), u32> = serde_json::from_str(json).unwrap();
+    assert!(g[0].parallel_edges(&1) == vec![(0, 7), (0, 8)]);
+    g[0].connect_keyed(&g[1], 2, 9).unwrap();
+    let json = serde_json::to_string(&g).unwrap();
+    assert!(json.ends_with("[[0,1,7],[0,1,8],[0,1,9]],[[2,2]]]"));
+
+    let json = "[[[0,null],[1,null]],[[0,1,7]],[[1,2]]]";
+    assert!(serde_json::from_str::<Graph<usize, (), u32>>(json).is_err());
+    let json = "[[[0,null],[1,null]],[[0,1,7]],[[0,0]]]";
+    assert!(serde_json::from_str::<Graph<usize, (), u32>>(json).is_err());
+}
+
+#[test]
+fn ut_digraph_multigraph_serde_cbor() {
+    use gdsl::digraph::*;
+
+    let mut g = Graph::<char, u8, &str>::new();
+    g.insert(Node::new('a', 1));
+    g.insert(Node::new('b', 2));
+    g.insert(Node::new('c', 3));
+
+    g['a'].connect(&g['b'], "walk");
+    g['a'].connect_keyed(&g['b'], 3, "rail").unwrap();
+    g['a'].connect_keyed(&g['b'], 5, "bus").unwrap();
+    g['b'].connect_keyed(&g['c'], 3, "rail").unwrap();
+
+    let cbor = serde_cbor::to_vec(&g).unwrap();
+    let de: Graph<char, u8, String> = serde_cbor::from_slice(&cbor).unwrap();
+
+    assert!(de.len() == 3);
+    assert!(*de['c'].value() == 3);
+    for (u, v) in [('a', 'b'), ('b', 'c'), ('a', 'c')] {
+        let expected: Vec<(usize, String)> = g[u]
+            .parallel_edges(&v)
+            .into_iter()
+            .map(|(key, value)| (key, value.to_string()))
+            .collect();
+        assert!(de[u].parallel_edges(&v) == expected);
+    }
+    assert!(de['a'].parallel_edges(&'b').len() == 3);
+}
+
+#[test]
+fn ut_digraph_traits() {
+    use gdsl::traits::*;
+
+    // The keys of the nodes reachable from `root`, in ascending order.
+    fn reachable<N>(root: &N) -> Vec<usize>
+    where
+        N: Neighbors<Key = usize>,
+    {
+        let mut visited = vec![*root.key()];
+        let mut stack = vec![root.clone()];
+        while let Some(u) = stack.pop() {
+            for (v, _) in u.successors() {
+                if !visited.contains(v.key()) {
+                    visited.push(*v.key());
+                    stack.push(v);
+                }
+            }
+        }
+        visited.remove(0);
+        visited.sort();
+        visited
+    }
+
+    fn sorted<N>(adjacent: impl Iterator<Item = (N, u32)>) -> Vec<(usize, u32)>
+    where
+        N: NodeRef<Key = usize>,
+    {
+        let mut adjacent = adjacent.map(|(v, e)| (*v.key(), e)).collect::<Vec<_>>();
+        adjacent.sort();
+        adjacent
+    }
+
+    // Written once and run with both the `Rc` and the `Arc` based graphs.
+    fn check<G>(g: &G)
+    where
+        G: GraphBase + Directed,
+        G::Node: NodeRef<Key = usize, EdgeValue = u32>,
+    {
+        assert!(g.node_count() == 4);
+        assert!(g.contains_node(&3) && !g.contains_node(&4));
+
+        let n0 = g.node(&0).unwrap();
+        let n2 = g.node(&2).unwrap();
+        assert!(sorted(n0.successors()) == vec![(1, 10), (2, 20)]);
+        assert!(n0.predecessors().next().is_none());
+        assert!(sorted(n2.predecessors()) == vec![(0, 20), (1, 12)]);
+        assert!(n2.successors().next().is_none());
+
+        assert!(reachable(&n0) == vec![1, 2]);
+        assert!(reachable(&n2).is_empty());
+        assert!(reachable(&g.node(&3).unwrap()).is_empty());
+    }
+
+    let mut g1 = gdsl::digraph::Graph::<usize, (), u32>::new();
+    let mut g2 = gdsl::sync_digraph::Graph::<usize, (), u32>::new();
+    for i in 0..4 {
+        g1.insert(gdsl::digraph::Node::new(i, ()));
+        g2.insert(gdsl::sync_digraph::Node::new(i, ()));
+    }
+    for (u, v, e) in [(0, 1, 10), (0, 2, 20), (1, 2, 12)] {
+        g1[u].connect(&g1[v], e);
+        g2[u].connect(&g2[v], e);
+    }
+    check(&g1);
+    check(&g2);
+}
+
+#[test]
+fn ut_digraph_freeze() {
+    use gdsl::digraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..20 {
+        let mut g = Graph::<usize, u32, u64>::new();
+        for i in 0..8 {
+            g.insert(Node::new(i, rng.gen_range(0..4)));
+        }
+        for _ in 0..20 {
+            let u = rng.gen_range(0..8);
+            let v = rng.gen_range(0..8);
+            g[u].connect(&g[v], rng.gen_range(1..10));
+        }
+
+        let f = g.freeze();
+        let idx = |i: usize| f.index(&i).unwrap();
+        assert!(f.len() == 8 && f.edge_count() == 20);
+        for i in 0..8 {
+            assert!(*f.key(idx(i)) == i && f.value(idx(i)) == g[i].value());
+        }
+
+        // Lengths of the shortest paths between all pairs of nodes.
+        let mut dist = [[None::<u64>; 8]; 8];
+        for (i, row) in dist.iter_mut().enumerate() {
+            row[i] = Some(0);
+            for (v, w) in f.edges(i as u32) {
+                let w = row[v as usize].map_or(*w, |d| d.min(*w));
+                row[v as usize] = Some(w);
+            }
+        }
+        for k in 0..8 {
+            for i in 0..8 {
+                for j in 0..8 {
+                    if let (Some(a), Some(b)) = (dist[i][k], dist[k][j]) {
+                        if dist[i][j].is_none_or(|d| a + b < d) {
+                            dist[i][j] = Some(a + b);
+                        }
+                    }
+                }
+            }
+        }
+
+        for root in 0..8 {
+            let node = &g[*f.key(root)];
+            let mut bfs = vec![root];
+            bfs.extend(Bfs::new(node).map(|edge| idx(*edge.target().key())));
+            assert!(f.bfs(root) == bfs);
+            let mut dfs = vec![root];
+            dfs.extend(Dfs::new(node).map(|edge| idx(*edge.target().key())));
+            assert!(f.dfs(root) == dfs);
+
+            let mut pfs = vec![root];
+            loop {
+                let next = pfs
+                    .iter()
+                    .flat_map(|&u| f.successors(u))
+                    .filter(|v| !pfs.contains(v))
+                    .min_by_key(|&&v| (f.value(v), v));
+                match next {
+                    Some(&v) => pfs.push(v),
+                    None => break,
+                }
+            }
+            assert!(f.pfs(root) == pfs);
+
+            let (distances, _) = f.shortest_paths(root, &|w| *w);
+            for target in 0..8 {
+                assert!(distances[target as usize] == dist[root as usize][target as usize]);
+                match f.shortest_path(root, target, &|w| *w) {
+                    Some((d, path)) => {
+                        assert!(Some(d) == dist[root as usize][target as usize]);
+                        assert!(path[0] == root && path[path.len() - 1] == target);
+                        let len = path
+                            .windows(2)
+                            .map(|w| {
+                                f.edges(w[0])
+                                    .filter(|e| e.0 == w[1])
+                                    .map(|e| *e.1)
+                                    .min()
+                                    .unwrap()
+                            })
+                            .sum::<u64>();
+                        assert!(len == d);
+                    }
+                    None => assert!(dist[root as usize][target as usize].is_none()),
+                }
+            }
+        }
+
+        let components = f.scc();
+        assert!(components.iter().map(|c| c.len()).sum::<usize>() == 8);
+        for component in &components {
+            for &u in component {
+                for v in 0..8u32 {
+                    let mutual = dist[u as usize][v as usize].is_some()
+                        && dist[v as usize][u as usize].is_some();
+                    assert!(mutual == component.contains(&v));
+                }
+            }
+        }
+
+        let thawed = f.thaw();
+        assert!(thawed.len() == 8);
+        for u in 0..8 {
+            assert!(thawed[u].value() == g[u].value());
+            for v in 0..8 {
+                assert!(thawed[u].parallel_edges(&v) == g[u].parallel_edges(&v));
+            }
+        }
+    }
+}
+
 #[test]
 fn ttt() {
     use gdsl::digraph::*;
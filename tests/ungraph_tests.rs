@@ -3,7 +3,7 @@ fn ut_ungraph_manual_bfs() {
     use gdsl::ungraph::*;
     use std::collections::{HashSet, VecDeque};
 
-    let g = vec![
+    let g = [
         Node::new(0, ()),
         Node::new(1, ()),
         Node::new(2, ()),
@@ -24,7 +24,7 @@ fn ut_ungraph_manual_bfs() {
     let mut queue = VecDeque::new();
 
     queue.push_back(g[0].clone());
-    visited.insert(g[0].key().clone());
+    visited.insert(*g[0].key());
 
     while let Some(node) = queue.pop_front() {
         for Edge(_, v, _) in &node {
@@ -32,7 +32,7 @@ fn ut_ungraph_manual_bfs() {
                 if v == g[4] {
                     return;
                 }
-                visited.insert(v.key().clone());
+                visited.insert(*v.key());
                 queue.push_back(v);
             }
         }
@@ -138,6 +138,36 @@ fn ut_ungraph_disconnect() {
     assert!(!n1.is_connected(n2.key()));
 }
 
+#[test]
+fn ut_ungraph_disconnect_both_endpoints() {
+    use gdsl::ungraph::*;
+
+    let n1 = Node::new(1, ());
+    let n2 = Node::new(2, ());
+
+    n1.connect(&n2, 'a');
+    n2.connect(&n1, 'b');
+    n1.connect(&n1, 'c');
+
+    // The edge is removed from both endpoints whichever side owns it.
+    assert!(n2.disconnect(n1.key()).unwrap() == 'a');
+    assert!(n1.degree() == 3 && n2.degree() == 1);
+    assert!(n1.disconnect(n2.key()).unwrap() == 'b');
+    assert!(n1.degree() == 2 && n2.degree() == 0);
+    assert!(!n1.is_connected(n2.key()) && !n2.is_connected(n1.key()));
+    assert!(n1.disconnect(n2.key()).is_err());
+
+    // A loop is in both adjacency lists of its node.
+    assert!(n1.disconnect(n1.key()).unwrap() == 'c');
+    assert!(n1.degree() == 0);
+
+    let s1 = gdsl::sync_ungraph::Node::new(1, ());
+    let s2 = gdsl::sync_ungraph::Node::new(2, ());
+    s1.connect(&s2, 'a');
+    assert!(s2.disconnect(s1.key()).unwrap() == 'a');
+    assert!(s1.degree() == 0 && s2.degree() == 0);
+}
+
 #[test]
 fn ut_ungraph_isolate() {
     use gdsl::ungraph::*;
@@ -312,8 +342,24 @@ fn ut_ungraph_sizes() {
     n1.connect(&n1t2, ());
     n1.connect(&n1t3, ());
 
-    assert!(n1.sizeof() == 96);
-    assert!(n1t1.sizeof() == 73);
+    assert!(n1.sizeof() == 216);
+    assert!(n1t1.sizeof() == 96);
+}
+
+#[test]
+fn ut_ungraph_sizes_shared_edges() {
+    use gdsl::ungraph::*;
+
+    let n1 = Node::<usize, (), u64>::new(1, ());
+    let n2 = Node::<usize, (), u64>::new(2, ());
+    let (s1, s2) = (n1.sizeof(), n2.sizeof());
+
+    n1.connect(&n2, 7);
+
+    // Both ends hold a 24 byte list entry and the source also counts the
+    // 32 byte shared value with its reference counts.
+    assert!(n1.sizeof() == s1 + 56);
+    assert!(n2.sizeof() == s2 + 24);
 }
 
 #[test]
@@ -356,3 +402,1251 @@ fn ut_ungraph_dijkstra() {
     assert!(g['H'].get() == 8);
     assert!(g['I'].get() == 14);
 }
+
+#[test]
+fn ut_ungraph_shortest_paths() {
+    use gdsl::*;
+
+    let g = ungraph![
+        (char) => [u64]
+        ('A') => [ ('B', 4), ('H', 8) ]
+        ('B') => [ ('C', 8), ('H', 11) ]
+        ('C') => [ ('D', 7), ('F', 4), ('I', 2) ]
+        ('D') => [ ('E', 9), ('F', 14) ]
+        ('E') => [ ('F', 10) ]
+        ('F') => [ ('G', 2) ]
+        ('G') => [ ('H', 1), ('I', 6) ]
+        ('H') => [ ('I', 7) ]
+        ('I') => []
+    ];
+
+    let paths = g.shortest_paths(&'A', &|e| *e).unwrap();
+
+    assert!(paths.distance(&'A') == Some(0));
+    assert!(paths.distance(&'B') == Some(4));
+    assert!(paths.distance(&'C') == Some(12));
+    assert!(paths.distance(&'D') == Some(19));
+    assert!(paths.distance(&'E') == Some(21));
+    assert!(paths.distance(&'F') == Some(11));
+    assert!(paths.distance(&'G') == Some(9));
+    assert!(paths.distance(&'H') == Some(8));
+    assert!(paths.distance(&'I') == Some(14));
+
+    let distances: &std::collections::HashMap<char, u64> = paths.distances();
+    assert!(distances.len() == 9);
+
+    let path = g['I'].dijkstra(&|e| *e).target(&'A').search_path().unwrap();
+
+    assert!(
+        path.to_vec_nodes()
+            == vec![
+                g['I'].clone(),
+                g['C'].clone(),
+                g['B'].clone(),
+                g['A'].clone()
+            ]
+    );
+}
+
+#[test]
+fn ut_ungraph_components() {
+    use gdsl::ungraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    // Count the components of the graph formed by `edges` with the node
+    // `skip` and the edge at index `cut` left out.
+    fn count(n: usize, edges: &[(usize, usize)], skip: Option<usize>, cut: Option<usize>) -> usize {
+        let mut parent = (0..n).collect::<Vec<_>>();
+        fn find(parent: &mut Vec<usize>, x: usize) -> usize {
+            if parent[x] != x {
+                parent[x] = find(parent, parent[x]);
+            }
+            parent[x]
+        }
+        for (i, &(u, v)) in edges.iter().enumerate() {
+            if Some(i) == cut || Some(u) == skip || Some(v) == skip {
+                continue;
+            }
+            let (ru, rv) = (find(&mut parent, u), find(&mut parent, v));
+            parent[ru] = rv;
+        }
+        (0..n)
+            .filter(|&x| Some(x) != skip && find(&mut parent, x) == x)
+            .count()
+    }
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..50 {
+        let n = 20;
+        let mut g = Graph::<usize, (), usize>::new();
+        let mut edges = vec![];
+        for i in 0..n {
+            g.insert(Node::new(i, ()));
+        }
+        for i in 0..rng.gen_range(10..30) {
+            let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+            g[u].connect(&g[v], i);
+            edges.push((u, v));
+        }
+        let components = count(n, &edges, None, None);
+
+        assert!(g.connected_components().len() == components);
+        assert!(
+            g.connected_components()
+                .iter()
+                .map(|c| c.len())
+                .sum::<usize>()
+                == n
+        );
+
+        let mut bridges = g.bridges().iter().map(|e| *e.value()).collect::<Vec<_>>();
+        bridges.sort();
+        let expected = (0..edges.len())
+            .filter(|&i| count(n, &edges, None, Some(i)) > components)
+            .collect::<Vec<_>>();
+        assert!(bridges == expected);
+
+        let mut points = g
+            .articulation_points()
+            .iter()
+            .map(|n| *n.key())
+            .collect::<Vec<_>>();
+        points.sort();
+        let expected = (0..n)
+            .filter(|&x| {
+                let isolated = edges.iter().all(|&(u, v)| u != x && v != x || u == v);
+                count(n, &edges, Some(x), None) > components - isolated as usize
+            })
+            .collect::<Vec<_>>();
+        assert!(points == expected);
+
+        // Every edge except self loops belongs to exactly one biconnected
+        // component and articulation points are shared between components.
+        let bcc = g.biconnected_components();
+        let mut values = bcc.iter().flatten().map(|e| *e.value()).collect::<Vec<_>>();
+        values.sort();
+        let expected = (0..edges.len())
+            .filter(|&i| edges[i].0 != edges[i].1)
+            .collect::<Vec<_>>();
+        assert!(values == expected);
+
+        let mut shared = (0..n)
+            .filter(|&x| {
+                bcc.iter()
+                    .filter(|c| {
+                        c.iter()
+                            .any(|e| e.source().key() == &x || e.target().key() == &x)
+                    })
+                    .count()
+                    > 1
+            })
+            .collect::<Vec<_>>();
+        shared.sort();
+        assert!(shared == points);
+        for bridge in g.bridges() {
+            assert!(bcc.iter().any(|c| c.len() == 1 && c[0] == bridge));
+        }
+    }
+}
+
+#[test]
+fn ut_ungraph_minimum_spanning_forest() {
+    use gdsl::ungraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    // With distinct weights the minimum spanning forest is unique, so both
+    // algorithms must pick exactly the same edges. An edge belongs to it iff
+    // its endpoints aren't connected through strictly lighter edges.
+    for _ in 0..30 {
+        let n = 15;
+        let mut g = Graph::<usize, (), u64>::new();
+        for i in 0..n {
+            g.insert(Node::new(i, ()));
+        }
+        let mut weights = (0..40u64).collect::<Vec<_>>();
+        for i in (1..weights.len()).rev() {
+            weights.swap(i, rng.gen_range(0..=i));
+        }
+        let mut edges = vec![];
+        for w in weights.into_iter().take(rng.gen_range(5..40)) {
+            let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+            g[u].connect(&g[v], w);
+            edges.push((u, v, w));
+        }
+
+        let mut expected = edges
+            .iter()
+            .filter(|&&(u, v, w)| {
+                let mut reached = vec![u];
+                let mut stack = vec![u];
+                while let Some(x) = stack.pop() {
+                    for &(a, b, c) in &edges {
+                        if c < w {
+                            for (s, t) in [(a, b), (b, a)] {
+                                if s == x && !reached.contains(&t) {
+                                    reached.push(t);
+                                    stack.push(t);
+                                }
+                            }
+                        }
+                    }
+                }
+                !reached.contains(&v)
+            })
+            .map(|&(_, _, w)| w)
+            .collect::<Vec<_>>();
+        expected.sort();
+
+        for (forest, total) in [g.kruskal(&|e| *e), g.prim(&|e| *e)] {
+            let mut chosen = forest.iter().map(|e| *e.value()).collect::<Vec<_>>();
+            chosen.sort();
+            assert!(chosen == expected);
+            assert!(total == expected.iter().sum::<u64>());
+        }
+        let components = g.connected_components().len();
+        assert!(g.minimum_spanning_forest(&|e| *e).0.len() == n - components);
+    }
+}
+
+#[test]
+fn ut_ungraph_centrality() {
+    use gdsl::ungraph::*;
+
+    let n = 6;
+    let mut g = Graph::<usize, (), ()>::new();
+    for i in 0..n {
+        g.insert(Node::new(i, ()));
+    }
+    for i in 1..n {
+        g[0].connect(&g[i], ());
+    }
+
+    let betweenness = g.betweenness_centrality();
+    let closeness = g.closeness_centrality();
+    let degree = g.degree_centrality();
+    let eigen = g.eigenvector_centrality(1e-12, 1000).unwrap();
+    let rank = g.page_rank().compute();
+
+    assert!((betweenness[&0] - 1.0).abs() < 1e-12);
+    assert!(closeness[&0] == 1.0);
+    assert!(degree[&0] == 1.0);
+    assert!((rank.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    for i in 1..n {
+        assert!(betweenness[&i] == 0.0);
+        assert!(closeness[&i] == (n - 1) as f64 / (1 + 2 * (n - 2)) as f64);
+        assert!(degree[&i] == 1.0 / (n - 1) as f64);
+        assert!(eigen[&0] > eigen[&i]);
+        assert!(rank[&0] > rank[&i]);
+        assert!((rank[&i] - rank[&1]).abs() < 1e-9);
+    }
+
+    // Personalizing on a leaf moves rank to it.
+    let personalization = std::collections::HashMap::from([(1, 1.0)]);
+    let rank = g.page_rank().personalization(personalization).compute();
+    assert!(rank[&1] > rank[&2]);
+
+    // A personalization without any weight falls back to the uniform one.
+    let zeros = (0..n).map(|i| (i, 0.0)).collect();
+    let rank = g.page_rank().personalization(zeros).compute();
+    let uniform = g.page_rank().compute();
+    for i in 0..n {
+        assert!(rank[&i].is_finite() && rank[&i] == uniform[&i]);
+    }
+    assert!(Graph::<usize, (), ()>::new()
+        .page_rank()
+        .compute()
+        .is_empty());
+
+    // Every node of a cycle is equally central.
+    let mut g = Graph::<usize, (), ()>::new();
+    for i in 1..n {
+        g.insert(Node::new(i, ()));
+    }
+    for i in 1..n {
+        g[i].connect(&g[i % (n - 1) + 1], ());
+    }
+
+    let betweenness = g.betweenness_centrality();
+    let eigen = g.eigenvector_centrality(1e-12, 1000).unwrap();
+    for i in 2..n {
+        assert!((betweenness[&i] - betweenness[&1]).abs() < 1e-12);
+        assert!((eigen[&i] - eigen[&1]).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn ut_ungraph_bipartition_error() {
+    use gdsl::{error::Error, *};
+
+    // An odd cycle converts into the crate error with `?`.
+    fn sides(g: &ungraph::Graph<char, (), ()>) -> Result<(usize, usize), Error> {
+        let (a, b) = g.bipartition()?;
+        Ok((a.len(), b.len()))
+    }
+
+    let g = ungraph![
+        (char)
+        ('A') => ['B']
+        ('B') => ['C']
+        ('C') => []
+    ];
+
+    assert!(matches!(sides(&g), Ok((1, 2)) | Ok((2, 1))));
+    g['C'].connect(&g['A'], ());
+    assert!(matches!(sides(&g), Err(Error::OddCycle)));
+}
+
+#[test]
+fn ut_ungraph_bipartite_matching() {
+    use gdsl::ungraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    // Best (size, -weight) over all matchings of the left nodes `from..`.
+    fn brute(edges: &[(usize, usize, i32)], from: usize, used: &mut Vec<usize>) -> (usize, i32) {
+        if from == 5 {
+            return (0, 0);
+        }
+        let mut best = brute(edges, from + 1, used);
+        for &(l, r, w) in edges {
+            if l == from && !used.contains(&r) {
+                used.push(r);
+                let (size, cost) = brute(edges, from + 1, used);
+                used.pop();
+                if (size + 1, -(cost + w)) > (best.0, -best.1) {
+                    best = (size + 1, cost + w);
+                }
+            }
+        }
+        best
+    }
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..50 {
+        let mut g = Graph::<usize, (), i32>::new();
+        let mut edges = vec![];
+        for i in 0..5 {
+            g.insert(Node::new(i, ()));
+            g.insert(Node::new(10 + i, ()));
+        }
+        for _ in 0..rng.gen_range(0..15) {
+            let (l, r, w) = (
+                rng.gen_range(0..5),
+                rng.gen_range(10..15),
+                rng.gen_range(-5..10),
+            );
+            match rng.gen_bool(0.5) {
+                true => g[l].connect(&g[r], w),
+                false => g[r].connect(&g[l], w),
+            }
+            edges.push((l, r, w));
+        }
+
+        let (a, b) = g.bipartition().unwrap();
+        assert!(a.len() + b.len() == 10);
+        for (_, node) in g.iter() {
+            for Edge(u, v, _) in node {
+                assert!(a.contains(&u) != a.contains(&v));
+            }
+        }
+
+        let (size, cost) = brute(&edges, 0, &mut vec![]);
+        let matching = g.maximum_matching().unwrap();
+        let (assignment, total) = g.assignment(&|e| *e).unwrap();
+
+        assert!(matching.len() == size);
+        assert!(assignment.len() == size);
+        assert!(total == cost);
+        assert!(assignment.iter().map(|e| *e.value()).sum::<i32>() == total);
+        for m in [&matching, &assignment] {
+            let mut ends = m
+                .iter()
+                .flat_map(|Edge(u, v, _)| [*u.key(), *v.key()])
+                .collect::<Vec<_>>();
+            ends.sort();
+            ends.dedup();
+            assert!(ends.len() == 2 * size);
+        }
+    }
+
+    for _ in 0..50 {
+        let mut g = Graph::<usize, (), ()>::new();
+        for i in 0..10 {
+            g.insert(Node::new(i, ()));
+        }
+        for _ in 0..12 {
+            let (u, v) = (rng.gen_range(0..10), rng.gen_range(0..10));
+            g[u].connect(&g[v], ());
+        }
+        match g.bipartition() {
+            Ok(_) => assert!(g.is_bipartite()),
+            Err(OddCycle(cycle)) => {
+                assert!(cycle.edges.len() % 2 == 1);
+                assert!(
+                    cycle.first_edge().unwrap().source() == cycle.last_edge().unwrap().target()
+                );
+                for pair in cycle.edges.windows(2) {
+                    assert!(pair[0].target() == pair[1].source());
+                    assert!(pair[0].source().is_connected(pair[0].target().key()));
+                }
+                assert!(matches!(g.maximum_matching(), Err(OddCycle(_))));
+            }
+        }
+    }
+
+    // Every left node prefers the right node after its own, so the only
+    // perfect matching is found through augmenting paths spanning the chain.
+    let n = 100_000;
+    let mut g = Graph::<usize, (), ()>::new();
+    for i in 0..2 * n {
+        g.insert(Node::new(i, ()));
+    }
+    for i in 0..n {
+        if i + 1 < n {
+            g[i].connect(&g[n + i + 1], ());
+        }
+        g[i].connect(&g[n + i], ());
+    }
+    assert!(g.maximum_matching().unwrap().len() == n);
+}
+
+#[test]
+fn ut_ungraph_isomorphism() {
+    use gdsl::ungraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn count(g: &Graph<usize, (), u8>, u: usize, v: usize) -> usize {
+        g[u].iter().filter(|Edge(_, w, _)| *w.key() == v).count()
+    }
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..30 {
+        // A random graph and a relabeled copy of it.
+        let mut g = Graph::<usize, (), u8>::new();
+        let mut h = Graph::<usize, (), u8>::new();
+        let mut perm = (0..6).collect::<Vec<usize>>();
+        for i in (1..6).rev() {
+            perm.swap(i, rng.gen_range(0..=i));
+        }
+        for (i, j) in perm.iter().enumerate() {
+            g.insert(Node::new(i, ()));
+            h.insert(Node::new(100 + j, ()));
+        }
+        let mut edges = vec![];
+        for _ in 0..rng.gen_range(0..10) {
+            let (u, v, e) = (
+                rng.gen_range(0..6),
+                rng.gen_range(0..6),
+                rng.gen_range(0..2),
+            );
+            g[u].connect(&g[v], e);
+            h[100 + perm[u]].connect(&h[100 + perm[v]], e);
+            edges.push((perm[u], perm[v], e));
+        }
+
+        assert!(g.is_isomorphic(&h));
+        let same = |a: &u8, b: &u8| a == b;
+        let mappings = g
+            .isomorphism(&h)
+            .edge_match(&same)
+            .mappings()
+            .collect::<Vec<_>>();
+        assert!(!mappings.is_empty());
+        for m in &mappings {
+            for u in 0..6 {
+                for v in 0..6 {
+                    assert!(count(&h, u + 100, v + 100) == count(&g, m[&(u + 100)], m[&(v + 100)]));
+                }
+            }
+        }
+
+        let (u, v) = (rng.gen_range(0..6), rng.gen_range(0..6));
+        h[100 + perm[u]].connect(&h[100 + perm[v]], 0);
+        edges.push((perm[u], perm[v], 0));
+        assert!(!g.is_isomorphic(&h));
+
+        // Induced subgraph on the first four nodes of the copy, compared to
+        // a brute force over every injective mapping.
+        let mut p = Graph::<usize, (), u8>::new();
+        for i in 0..4 {
+            p.insert(Node::new(100 + i, ()));
+        }
+        for &(u, v, e) in &edges {
+            if u < 4 && v < 4 {
+                p[100 + u].connect(&p[100 + v], e);
+            }
+        }
+        let mut expected = 0;
+        for a in 0..6 {
+            for b in 0..6 {
+                for c in 0..6 {
+                    for d in 0..6 {
+                        let m = [a, b, c, d];
+                        if (0..4).any(|i| m[..i].contains(&m[i])) {
+                            continue;
+                        }
+                        let ok = (0..4).all(|i| {
+                            (0..4).all(|j| {
+                                count(&p, 100 + i, 100 + j) == count(&h, 100 + m[i], 100 + m[j])
+                            })
+                        });
+                        if ok {
+                            expected += 1;
+                        }
+                    }
+                }
+            }
+        }
+        assert!(h.subgraph_mappings(&p).count() == expected);
+        assert!(h.is_subgraph_isomorphic(&p));
+    }
+}
+
+#[test]
+fn ut_ungraph_isomorphism_parallel_edges() {
+    use gdsl::ungraph::*;
+
+    // A path 0 - 1 - 2 with the given parallel edges between 0 and 1.
+    fn build(parallel: [char; 3]) -> Graph<usize, (), char> {
+        let mut g = Graph::new();
+        for i in 0..3 {
+            g.insert(Node::new(i, ()));
+        }
+        for e in parallel {
+            g[0].connect(&g[1], e);
+        }
+        g[1].connect(&g[2], 'a');
+        g
+    }
+
+    let same = |a: &char, b: &char| a == b;
+
+    // The same parallel edges connected in a different order.
+    let g = build(['a', 'b', 'c']);
+    let h = build(['c', 'a', 'b']);
+    assert!(g.isomorphism(&h).edge_match(&same).is_isomorphic());
+    assert!(g.isomorphism(&h).edge_match(&same).is_subgraph_isomorphic());
+    let mut mappings = g.isomorphism(&h).edge_match(&same).mappings();
+    let mapping: std::collections::HashMap<usize, usize> = mappings.next().unwrap();
+    assert!((0..3).all(|i| mapping[&i] == i));
+
+    // The same number of parallel edges with other values.
+    let h = build(['b', 'a', 'b']);
+    assert!(g.is_isomorphic(&h));
+    assert!(!g.isomorphism(&h).edge_match(&same).is_isomorphic());
+}
+
+#[test]
+fn ut_ungraph_traversal_iterators() {
+    use gdsl::ungraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..30 {
+        let n = (0..8).map(|i| Node::new(i, i)).collect::<Vec<_>>();
+        for _ in 0..rng.gen_range(0..12) {
+            n[rng.gen_range(0..8)].connect(&n[rng.gen_range(0..8)], ());
+        }
+        let reached = n[0].order().post().search_nodes().len();
+
+        // Without a target every search visits every reachable node once.
+        assert!(n[0].bfs().count() == reached - 1);
+        assert!(n[0].dfs().count() == reached - 1);
+        assert!(n[0].pfs().count() == reached - 1);
+        assert!(n[0].dfs().collect::<Vec<_>>() == n[0].order().pre().collect::<Vec<_>>());
+
+        // With a target the iteration ends at the edge reaching it.
+        let target = rng.gen_range(1..8);
+        let searches = [
+            (
+                n[0].bfs().target(&target).collect::<Vec<_>>(),
+                n[0].bfs().target(&target).search_path(),
+            ),
+            (
+                n[0].dfs().target(&target).collect::<Vec<_>>(),
+                n[0].dfs().target(&target).search_path(),
+            ),
+        ];
+        for (edges, path) in searches {
+            let last = edges.last().map(|e| *e.target().key());
+            assert!(path.is_some() == (last == Some(target)));
+            if let Some(path) = path {
+                assert!(Path::from_edge_tree(edges).edges == path.edges);
+            }
+        }
+
+        // In post-order an edge comes after every edge below its target.
+        let post = n[0].order().post().collect::<Vec<_>>();
+        assert!(post.len() == reached - 1);
+        for (i, Edge(_, v, _)) in post.iter().enumerate() {
+            assert!(post[i..].iter().all(|Edge(u, _, _)| u != v));
+        }
+    }
+
+    let n = (0..6).map(|i| Node::new(i, i)).collect::<Vec<_>>();
+    for i in 0..5 {
+        n[i].connect(&n[i + 1], ());
+    }
+
+    let keys = n[0]
+        .bfs()
+        .take_while(|e| *e.target().key() != 4)
+        .map(|e| *e.target().key());
+    assert!(keys.collect::<Vec<_>>() == vec![1, 2, 3]);
+
+    for (a, b) in n[0].dfs().zip(n[0].pfs()) {
+        assert!(a == b);
+    }
+
+    let mut search = n[2].bfs();
+    let first = search.search_cycle().map(|path| path.len());
+    let second = search.search_cycle().map(|path| path.len());
+    assert!(first == second);
+}
+
+#[test]
+fn ut_ungraph_depth_limited_search() {
+    use gdsl::ungraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..50 {
+        let n = (0..8).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+        let mut adj = vec![vec![]; 8];
+        for _ in 0..rng.gen_range(0..14) {
+            let (u, v) = (rng.gen_range(0..8), rng.gen_range(0..8));
+            n[u].connect(&n[v], ());
+            adj[u].push(v);
+            adj[v].push(u);
+        }
+
+        // Distances from the root by a plain breadth-first search.
+        let mut dist = [usize::MAX; 8];
+        let mut queue = std::collections::VecDeque::from([0]);
+        dist[0] = 0;
+        while let Some(u) = queue.pop_front() {
+            for &v in &adj[u] {
+                if dist[v] == usize::MAX {
+                    dist[v] = dist[u] + 1;
+                    queue.push_back(v);
+                }
+            }
+        }
+
+        let max = rng.gen_range(0..4);
+        let within = (1..8).filter(|&v| dist[v] <= max).collect::<Vec<_>>();
+        let targets = |edges: Vec<Edge<usize, (), ()>>| {
+            let mut keys = edges.iter().map(|e| *e.target().key()).collect::<Vec<_>>();
+            keys.sort();
+            keys
+        };
+
+        assert!(targets(n[0].bfs().max_depth(max).collect()) == within);
+        assert!(targets(n[0].dfs().max_depth(max).collect()) == within);
+        assert!(
+            targets(
+                n[0].bfs()
+                    .filter_depth(&mut |_, depth| depth <= max)
+                    .collect()
+            ) == within
+        );
+
+        let mut depths = vec![];
+        let edges = n[0]
+            .dfs()
+            .iterative_deepening()
+            .max_depth(max)
+            .for_each_depth(&mut |_, depth| depths.push(depth))
+            .collect::<Vec<_>>();
+        assert!(depths.iter().all(|&depth| depth <= max));
+        let order = edges
+            .iter()
+            .map(|e| dist[*e.target().key()])
+            .collect::<Vec<_>>();
+        assert!(order.windows(2).all(|w| w[0] <= w[1]));
+        assert!(edges
+            .iter()
+            .all(|e| dist[*e.source().key()] + 1 == dist[*e.target().key()]));
+        assert!(targets(edges) == within);
+
+        let all = (1..8)
+            .filter(|&v| dist[v] != usize::MAX)
+            .collect::<Vec<_>>();
+        assert!(targets(n[0].dfs().iterative_deepening().collect()) == all);
+
+        let count = n[0]
+            .bfs()
+            .for_each_depth(&mut |Edge(u, _, _), depth| {
+                assert!(dist[*u.key()] + 1 == depth);
+            })
+            .count();
+        assert!(count == all.len());
+    }
+
+    // Every pass starts over from the root, so the closure sees the edge
+    // leaving the root once per pass, four times for a path of three edges.
+    let n = (0..4).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+    for i in 0..3 {
+        n[i].connect(&n[i + 1], ());
+    }
+    let mut calls = 0;
+    let edges = n[0]
+        .dfs()
+        .iterative_deepening()
+        .for_each(&mut |Edge(u, _, _)| {
+            if *u.key() == 0 {
+                calls += 1;
+            }
+        })
+        .count();
+    assert!(edges == 3);
+    assert!(calls == 4);
+}
+
+#[test]
+fn ut_ungraph_multi_source_search() {
+    use gdsl::ungraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..50 {
+        let n = (0..10).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+        let mut adj = vec![vec![]; 10];
+        for _ in 0..rng.gen_range(0..16) {
+            let (u, v) = (rng.gen_range(0..10), rng.gen_range(0..10));
+            n[u].connect(&n[v], ());
+            adj[u].push(v);
+            adj[v].push(u);
+        }
+        let seeds = (0..rng.gen_range(1..4))
+            .map(|_| rng.gen_range(0..10))
+            .collect::<Vec<usize>>();
+
+        // Distances from every node by a plain breadth-first search.
+        let dist = (0..10)
+            .map(|s| {
+                let mut dist = [usize::MAX; 10];
+                let mut queue = std::collections::VecDeque::from([s]);
+                dist[s] = 0;
+                while let Some(u) = queue.pop_front() {
+                    for &v in &adj[u] {
+                        if dist[v] == usize::MAX {
+                            dist[v] = dist[u] + 1;
+                            queue.push_back(v);
+                        }
+                    }
+                }
+                dist
+            })
+            .collect::<Vec<_>>();
+        let nearest = |v: usize| seeds.iter().map(|&s| dist[s][v]).min().unwrap();
+        let reached = (0..10)
+            .filter(|&v| !seeds.contains(&v) && nearest(v) != usize::MAX)
+            .collect::<Vec<_>>();
+        let roots = || seeds.iter().map(|&s| n[s].clone());
+        let targets = |edges: &[Edge<usize, (), ()>]| {
+            let mut keys = edges.iter().map(|e| *e.target().key()).collect::<Vec<_>>();
+            keys.sort();
+            keys
+        };
+
+        let mut bfs = Bfs::from_roots(roots());
+        let edges = bfs.by_ref().collect::<Vec<_>>();
+        assert!(targets(&edges) == reached);
+        let mut depth = [0; 10];
+        for Edge(u, v, _) in &edges {
+            depth[*v.key()] = depth[*u.key()] + 1;
+            assert!(depth[*v.key()] == nearest(*v.key()));
+            let origin = *bfs.origin(v.key()).unwrap();
+            assert!(seeds.contains(&origin) && dist[origin][*v.key()] == depth[*v.key()]);
+        }
+        assert!(seeds.iter().all(|s| bfs.origin(s) == Some(s)));
+
+        let mut dfs = Dfs::from_roots(roots());
+        let edges = dfs.by_ref().collect::<Vec<_>>();
+        assert!(targets(&edges) == reached);
+        for v in &reached {
+            let origin = *dfs.origin(v).unwrap();
+            assert!(seeds.contains(&origin) && dist[origin][*v] != usize::MAX);
+        }
+
+        let mut pfs = Pfs::from_roots(roots());
+        let edges = pfs.by_ref().collect::<Vec<_>>();
+        assert!(targets(&edges) == reached);
+        let distinct = seeds.iter().collect::<std::collections::HashSet<_>>().len();
+        let origins: &std::collections::HashMap<usize, usize> = pfs.origins();
+        assert!(origins.len() == reached.len() + distinct);
+
+        let t = rng.gen_range(0..10);
+        let path = Bfs::from_roots(roots()).target(&t).search_path();
+        match reached.contains(&t) {
+            true => {
+                let path = path.unwrap();
+                assert!(seeds.contains(path.first_edge().unwrap().source().key()));
+                assert!(path.len() == nearest(t) + 1);
+            }
+            false => assert!(path.is_none()),
+        }
+    }
+}
+
+#[test]
+fn ut_ungraph_dfs_visitor() {
+    use gdsl::ungraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Discover(usize),
+        Finish(usize),
+        Tree(usize, usize),
+        Back(usize, usize),
+        ForwardOrCross(usize, usize),
+    }
+
+    struct Recorder {
+        events: Vec<Event>,
+        prune: usize,
+        stop: bool,
+    }
+
+    impl Visitor<usize, (), ()> for Recorder {
+        fn discover_node(&mut self, node: &Node<usize, (), ()>) -> Control {
+            self.events.push(Event::Discover(*node.key()));
+            match *node.key() == self.prune {
+                true => Control::Prune,
+                false => Control::Continue,
+            }
+        }
+
+        fn finish_node(&mut self, node: &Node<usize, (), ()>) -> Control {
+            self.events.push(Event::Finish(*node.key()));
+            Control::Continue
+        }
+
+        fn tree_edge(&mut self, Edge(u, v, _): &Edge<usize, (), ()>) -> Control {
+            self.events.push(Event::Tree(*u.key(), *v.key()));
+            Control::Continue
+        }
+
+        fn back_edge(&mut self, Edge(u, v, _): &Edge<usize, (), ()>) -> Control {
+            self.events.push(Event::Back(*u.key(), *v.key()));
+            match self.stop {
+                true => Control::Stop,
+                false => Control::Continue,
+            }
+        }
+
+        fn forward_or_cross_edge(&mut self, Edge(u, v, _): &Edge<usize, (), ()>) -> Control {
+            self.events.push(Event::ForwardOrCross(*u.key(), *v.key()));
+            Control::Continue
+        }
+    }
+
+    // Recursive depth-first search over the adjacency lists that examines
+    // each edge once and skips the tree edge back to the parent.
+    fn reference(
+        adj: &[Vec<usize>],
+        u: usize,
+        mut parent: Option<usize>,
+        prune: usize,
+        state: &mut [u8],
+        events: &mut Vec<Event>,
+    ) {
+        state[u] = 1;
+        events.push(Event::Discover(u));
+        if u != prune {
+            for &v in &adj[u] {
+                if state[v] == 2 {
+                    continue;
+                }
+                if parent == Some(v) {
+                    parent = None;
+                    continue;
+                }
+                match state[v] {
+                    0 => {
+                        events.push(Event::Tree(u, v));
+                        reference(adj, v, Some(u), prune, state, events);
+                    }
+                    _ => events.push(Event::Back(u, v)),
+                }
+            }
+        }
+        state[u] = 2;
+        events.push(Event::Finish(u));
+    }
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..50 {
+        let n = (0..10).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+        let mut adj = vec![vec![]; 10];
+        let mut inbound = vec![vec![]; 10];
+        for _ in 0..rng.gen_range(0..20) {
+            let (u, v) = (rng.gen_range(0..10), rng.gen_range(0..10));
+            if u != v {
+                n[u].connect(&n[v], ());
+                adj[u].push(v);
+                inbound[v].push(u);
+            }
+        }
+        // Outbound edges are iterated before inbound ones.
+        for (u, inbound) in inbound.into_iter().enumerate() {
+            adj[u].extend(inbound);
+        }
+
+        let prune = rng.gen_range(0..20);
+        let mut expected = vec![];
+        let mut state = [0; 10];
+        for u in 0..10 {
+            if state[u] == 0 {
+                reference(&adj, u, None, prune, &mut state, &mut expected);
+            }
+        }
+
+        let mut recorder = Recorder {
+            events: vec![],
+            prune,
+            stop: false,
+        };
+        let control = Dfs::from_roots(n.iter().cloned()).visit(&mut recorder);
+        assert!(control == Control::Continue);
+        assert!(recorder.events == expected);
+
+        recorder.events.clear();
+        recorder.stop = true;
+        let control = Dfs::from_roots(n.iter().cloned()).visit(&mut recorder);
+        match expected.iter().position(|e| matches!(e, Event::Back(..))) {
+            Some(i) => {
+                assert!(control == Control::Stop);
+                assert!(recorder.events[..] == expected[..=i]);
+            }
+            None => assert!(control == Control::Continue),
+        }
+    }
+}
+
+#[test]
+fn ut_ungraph_shared_edge_values() {
+    use gdsl::ungraph::*;
+
+    let a = Node::new('a', ());
+    let b = Node::new('b', ());
+    let c = Node::new('c', ());
+
+    a.connect(&b, 1);
+    b.connect(&c, 100);
+
+    // An update from either end is seen from both ends of the edge.
+    b.update_edge(&'a', |w| *w += 1).unwrap();
+    c.update_edge(&'b', |w| *w += 1).unwrap();
+    assert!(a.edge_value(&'b', |w| *w) == Some(2));
+    assert!(b.edge_value(&'c', |w| *w) == Some(101));
+    assert!(a.iter().next().unwrap().value() == &2);
+    assert!(c.iter().next().unwrap().value() == &101);
+
+    // Nodes that are not adjacent have no edge value.
+    assert!(a.edge_value(&'c', |w| *w).is_none());
+    assert!(a.update_edge(&'c', |w| *w += 1).is_err());
+
+    // The removed value is the shared one.
+    assert!(c.disconnect(&'b').unwrap() == 101);
+    assert!(b.edge_value(&'c', |w| *w).is_none());
+}
+
+#[test]
+fn ut_ungraph_remove_and_dangling_edges() {
+    use gdsl::ungraph::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..50 {
+        let mut adj = [[false; 10]; 10];
+        for (u, v) in (0..10).flat_map(|u| (u + 1..10).map(move |v| (u, v))) {
+            let edge = rng.gen_range(0..4) == 0;
+            adj[u][v] = edge;
+            adj[v][u] = edge;
+        }
+        let alive = (0..10)
+            .map(|_| rng.gen_range(0..3) != 0)
+            .collect::<Vec<_>>();
+        let check = |n: &[Node<usize, (), ()>], adj: &[[bool; 10]; 10]| {
+            for u in n {
+                let mut adjacent = u.iter().map(|Edge(_, v, _)| *v.key()).collect::<Vec<_>>();
+                adjacent.sort();
+                let expected = (0..10)
+                    .filter(|&v| alive[v] && adj[*u.key()][v])
+                    .collect::<Vec<_>>();
+                assert!(adjacent == expected);
+                assert!(u.degree() == adjacent.len());
+                for (v, &live) in alive.iter().enumerate() {
+                    if !live {
+                        assert!(!u.is_connected(&v));
+                        assert!(u.disconnect(&v).is_err());
+                    }
+                }
+            }
+        };
+
+        // Removing a node from a graph detaches it from the rest.
+        let mut g = Graph::<usize, (), ()>::new();
+        for i in 0..10 {
+            g.insert(Node::new(i, ()));
+        }
+        for u in 0..10 {
+            for v in u + 1..10 {
+                if adj[u][v] {
+                    g[u].connect(&g[v], ());
+                }
+            }
+        }
+        for (u, &live) in alive.iter().enumerate() {
+            if !live {
+                assert!(g.remove(&u).unwrap().is_orphan());
+            }
+        }
+        check(&g.to_vec(), &adj);
+
+        // Dropping a connected node leaves dangling edges which are skipped.
+        let mut n = (0..10).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+        for u in 0..10 {
+            for v in u + 1..10 {
+                if adj[u][v] {
+                    n[u].connect(&n[v], ());
+                }
+            }
+        }
+        n.retain(|u| alive[*u.key()]);
+        check(&n, &adj);
+
+        // Connecting more nodes keeps skipping the dangling edges.
+        for (i, u) in n.iter().enumerate() {
+            for v in &n[i + 1..] {
+                if !adj[*u.key()][*v.key()] && rng.gen_range(0..4) == 0 {
+                    u.connect(v, ());
+                    adj[*u.key()][*v.key()] = true;
+                    adj[*v.key()][*u.key()] = true;
+                }
+            }
+        }
+        check(&n, &adj);
+        for u in &n {
+            u.isolate();
+        }
+        assert!(n.iter().all(|u| u.is_orphan()));
+    }
+
+    // Connecting during iteration leaves the edges ahead of the cursor in
+    // place, even with a dangling edge behind it, so none is skipped.
+    let n = (0..5).map(|i| Node::new(i, ())).collect::<Vec<_>>();
+    let dropped = Node::new(5, ());
+    n[0].connect(&dropped, ());
+    for v in &n[1..4] {
+        n[0].connect(v, ());
+    }
+    drop(dropped);
+    let mut targets = vec![];
+    for Edge(_, v, _) in n[0].iter() {
+        if targets.is_empty() {
+            n[0].connect(&n[4], ());
+        }
+        targets.push(*v.key());
+    }
+    assert!(targets == [1, 2, 3, 4]);
+}
+
+#[test]
+fn ut_ungraph_multigraph() {
+    use gdsl::{error::Error, ungraph::*};
+
+    let mut g = Graph::<usize, (), u32>::new();
+    g.insert(Node::new(0, ()));
+    g.insert(Node::new(1, ()));
+
+    g[0].connect(&g[1], 10);
+    g[0].connect(&g[1], 20);
+    g[0].connect_keyed(&g[1], 5, 50).unwrap();
+    g[0].connect_keyed(&g[1], 3, 30).unwrap();
+
+    // Keys other than `0` are unique and `0` is reserved for `connect`.
+    assert!(matches!(
+        g[0].connect_keyed(&g[1], 3, 31),
+        Err(Error::EdgeAlreadyExists)
+    ));
+    assert!(matches!(
+        g[0].connect_keyed(&g[1], 0, 0),
+        Err(Error::ReservedKey)
+    ));
+    assert!(g[0].parallel_edges(&1) == vec![(0, 10), (0, 20), (3, 30), (5, 50)]);
+
+    // Parallel edges are undirected and listed from both endpoints.
+    assert!(g[1].parallel_edges(&0) == g[0].parallel_edges(&1));
+    assert!(g[1].connect_keyed(&g[0], 3, 40).is_err());
+}
+
+#[test]
+fn ut_ungraph_multigraph_disconnect_edge() {
+    use gdsl::{error::Error, ungraph::*};
+
+    let mut g = Graph::<usize, (), u32>::new();
+    g.insert(Node::new(0, ()));
+    g.insert(Node::new(1, ()));
+
+    g[0].connect(&g[1], 10);
+    g[0].connect_keyed(&g[1], 3, 30).unwrap();
+    g[0].connect_keyed(&g[1], 5, 50).unwrap();
+
+    // Only the edge with the key is removed, from both endpoints.
+    assert!(g[0].disconnect_edge(&1, 3).unwrap() == 30);
+    assert!(matches!(
+        g[0].disconnect_edge(&1, 3),
+        Err(Error::EdgeNotFound)
+    ));
+    assert!(g[0].parallel_edges(&1) == vec![(0, 10), (5, 50)]);
+    assert!(g[1].degree() == 2);
+
+    // Edges made with `connect` can't be addressed by key.
+    assert!(matches!(
+        g[0].disconnect_edge(&1, 0),
+        Err(Error::ReservedKey)
+    ));
+    assert!(g[0].disconnect(&1).unwrap() == 10);
+    assert!(g[0].parallel_edges(&1) == vec![(5, 50)]);
+}
+
+#[test]
+fn ut_ungraph_multigraph_serde_json() {
+    use gdsl::ungraph::*;
+
+    let mut g = Graph::<usize, (), u32>::new();
+    g.insert(Node::new(0, ()));
+    g.insert(Node::new(1, ()));
+
+    g[0].connect(&g[1], 10);
+    g[0].connect_keyed(&g[1], 5, 50).unwrap();
+    g[0].connect_keyed(&g[1], 3, 30).unwrap();
+
+    let json = serde_json::to_string(&g).unwrap();
+    let de: Graph<usize, (), u32> = serde_json::from_str(&json).unwrap();
+    assert!(de[0].parallel_edges(&1) == g[0].parallel_edges(&1));
+
+    // Data serialized before edges had keys still loads. The edge list keeps
+    // that format and the keys other than `0` follow it by edge index.
+    let json = "[[[0,null],[1,null]],[[0,1,7],[0,1,8]]]";
+    let g: Graph<usize, (), u32> = serde_json::from_str(json).unwrap();
+    assert!(g[0].parallel_edges(&1) == vec![(0, 7), (0, 8)]);
+    g[0].connect_keyed(&g[1], 2, 9).unwrap();
+    let json = serde_json::to_string(&g).unwrap();
+    assert!(json.ends_with("[[0,1,7],[0,1,8],[0,1,9]],[[2,2]]]"));
+
+    let json = "[[[0,null],[1,null]],[[0,1,7]],[[1,2]]]";
+    assert!(serde_json::from_str::<Graph<usize, (), u32>>(json).is_err());
+    let json = "[[[0,null],[1,null]],[[0,1,7]],[[0,0]]]";
+    assert!(serde_json::from_str::<Graph<usize, (), u32>>(json).is_err());
+}
+
+#[test]
+fn ut_ungraph_multigraph_serde_cbor() {
+    use gdsl::ungraph::*;
+
+    let mut g = Graph::<char, u8, &str>::new();
+    g.insert(Node::new('a', 1));
+    g.insert(Node::new('b', 2));
+    g.insert(Node::new('c', 3));
+
+    g['a'].connect(&g['b'], "walk");
+    g['a'].connect_keyed(&g['b'], 3, "rail").unwrap();
+    g['a'].connect_keyed(&g['b'], 5, "bus").unwrap();
+    g['b'].connect_keyed(&g['c'], 3, "rail").unwrap();
+
+    let cbor = serde_cbor::to_vec(&g).unwrap();
+    let de: Graph<char, u8, String> = serde_cbor::from_slice(&cbor).unwrap();
+
+    assert!(de.len() == 3);
+    assert!(*de['c'].value() == 3);
+    for (u, v) in [('a', 'b'), ('b', 'c'), ('a', 'c')] {
+        let expected: Vec<(usize, String)> = g[u]
+            .parallel_edges(&v)
+            .into_iter()
+            .map(|(key, value)| (key, value.to_string()))
+            .collect();
+        assert!(de[u].parallel_edges(&v) == expected);
+    }
+    assert!(de['a'].parallel_edges(&'b').len() == 3);
+}
+
+#[test]
+fn ut_ungraph_traits() {
+    use gdsl::traits::*;
+
+    // The keys of the nodes reachable from `root`, in ascending order.
+    fn reachable<N>(root: &N) -> Vec<usize>
+    where
+        N: Neighbors<Key = usize>,
+    {
+        let mut visited = vec![*root.key()];
+        let mut stack = vec![root.clone()];
+        while let Some(u) = stack.pop() {
+            for (v, _) in u.successors() {
+                if !visited.contains(v.key()) {
+                    visited.push(*v.key());
+                    stack.push(v);
+                }
+            }
+        }
+        visited.remove(0);
+        visited.sort();
+        visited
+    }
+
+    fn sorted<N>(adjacent: impl Iterator<Item = (N, u32)>) -> Vec<(usize, u32)>
+    where
+        N: NodeRef<Key = usize>,
+    {
+        let mut adjacent = adjacent.map(|(v, e)| (*v.key(), e)).collect::<Vec<_>>();
+        adjacent.sort();
+        adjacent
+    }
+
+    // Written once and run with both the `Rc` and the `Arc` based graphs.
+    fn check<G>(g: &G)
+    where
+        G: GraphBase,
+        G::Node: NodeRef<Key = usize, EdgeValue = u32>,
+    {
+        assert!(g.node_count() == 4);
+        assert!(g.contains_node(&3) && !g.contains_node(&4));
+
+        let n0 = g.node(&0).unwrap();
+        let n2 = g.node(&2).unwrap();
+        assert!(sorted(n0.successors()) == vec![(1, 10), (2, 20)]);
+        assert!(sorted(n0.predecessors()) == vec![(1, 10), (2, 20)]);
+        assert!(sorted(n2.successors()) == vec![(0, 20), (1, 12)]);
+
+        assert!(reachable(&n0) == vec![1, 2]);
+        assert!(reachable(&n2) == vec![0, 1]);
+        assert!(reachable(&g.node(&3).unwrap()).is_empty());
+    }
+
+    let mut g1 = gdsl::ungraph::Graph::<usize, (), u32>::new();
+    let mut g2 = gdsl::sync_ungraph::Graph::<usize, (), u32>::new();
+    for i in 0..4 {
+        g1.insert(gdsl::ungraph::Node::new(i, ()));
+        g2.insert(gdsl::sync_ungraph::Node::new(i, ()));
+    }
+    for (u, v, e) in [(0, 1, 10), (0, 2, 20), (1, 2, 12)] {
+        g1[u].connect(&g1[v], e);
+        g2[u].connect(&g2[v], e);
+    }
+    check(&g1);
+    check(&g2);
+}